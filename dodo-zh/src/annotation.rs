@@ -0,0 +1,545 @@
+use crate::cedict::{Dictionary, Item, PolyphoneIndex};
+use crate::error::Error;
+use crate::polyphone::{Ambiguity, PolyphoneRules, Resolution, UnappliedRule};
+use crate::segmenter::{SegmentMode, Segmenter, Token, TokenKind};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// PinyinFormat is the romanization used to render the reading of an annotated text
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinyinFormat {
+    /// Pinyin with tone numbers as written in the cedict file e.g: ni3 hao3
+    #[default]
+    ToneNumber,
+    /// Pinyin with tone marks. The tone marks are combining characters e.g: `ni\u{30c} ha\u{30c}o`
+    ToneMark,
+    /// Zhuyin converted from the pinyin with tone marks. The tones are combining characters e.g: `ㄨㄛ\u{30c}`
+    Zhuyin,
+    /// Wade-Giles with tone numbers e.g: ni3 hao3
+    WadeGiles,
+}
+
+/// AnnotatedToken is a token of a segmented text with the entry used to get its reading
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnnotatedToken<'d, 't> {
+    pub text: &'t str,
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+    /// Entry whose pinyin is used for the token. It's `None` when the token isn't a headword of the dictionary
+    pub item: Option<&'d Item>,
+    /// Reading of the token with tone numbers. It's empty when the token has no reading e.g: punctuations
+    pub pinyin_tone_number: Vec<String>,
+    /// Characters of the token which have several readings in the dictionary
+    pub ambiguities: Vec<Ambiguity>,
+}
+
+impl AnnotatedToken<'_, '_> {
+    /// Get the reading of the token in the given format. It's `None` when the token has no reading
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `format` - PinyinFormat
+    pub fn pinyin(&self, format: PinyinFormat) -> Result<Option<String>, Error> {
+        if self.pinyin_tone_number.is_empty() {
+            return Ok(None);
+        }
+
+        let pinyin = self.pinyin_tone_number.join(" ");
+        let res = match format {
+            PinyinFormat::ToneNumber => pinyin,
+            PinyinFormat::ToneMark => crate::convert_pinyin_tone_number_to_tone_mark(pinyin)?,
+            PinyinFormat::Zhuyin => crate::convert_pinyin_to_zhuyin(
+                crate::convert_pinyin_tone_number_to_tone_mark(pinyin)?,
+            )?,
+            PinyinFormat::WadeGiles => crate::convert_pinyin_to_wade_giles(pinyin)?,
+        };
+
+        Ok(Some(res))
+    }
+
+    /// Check whether the token has one syllable per character
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    fn is_aligned(&self) -> bool {
+        self.text.chars().count() == self.pinyin_tone_number.len()
+    }
+}
+
+/// Annotation is a text split into tokens which have the reading of their headword
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::annotation::PinyinFormat;
+/// use dodo_zh::cedict::Dictionary;
+/// use dodo_zh::variant::KeyVariant;
+///
+/// let dictionary = Dictionary::from_str("你好 你好 [ni3 hao3] /hello/\n朋友 朋友 [peng2 you5] /friend/", KeyVariant::Simplified).unwrap();
+/// let annotation = dictionary.annotate("你好, 朋友!");
+///
+/// assert_eq!(annotation.to_pinyin(PinyinFormat::ToneNumber).unwrap(), "ni3 hao3, peng2 you5!");
+/// assert_eq!(annotation.to_pinyin(PinyinFormat::ToneMark).unwrap(), "ni\u{30c} ha\u{30c}o, pe\u{301}ng you!");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Annotation<'d, 't> {
+    pub tokens: Vec<AnnotatedToken<'d, 't>>,
+    /// Rules which match the text but could not be applied e.g: the phrase cover a headword whose number of
+    /// syllables does not match its number of characters
+    pub unapplied_rules: Vec<UnappliedRule>,
+}
+
+impl Annotation<'_, '_> {
+    /// Get every characters of the text which have several readings in the dictionary with the reading
+    /// which has been chosen
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub fn ambiguities(&self) -> Vec<&Ambiguity> {
+        self.tokens
+            .iter()
+            .flat_map(|token| &token.ambiguities)
+            .collect()
+    }
+
+    /// Render the whole text in the given format. The readings of two consecutive words are separated by
+    /// a space and the tokens without an entry e.g: punctuations or latin words are kept as is
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `format` - PinyinFormat
+    pub fn to_pinyin(&self, format: PinyinFormat) -> Result<String, Error> {
+        let mut res = String::new();
+        let mut previous_is_reading = false;
+
+        for token in &self.tokens {
+            match token.pinyin(format)? {
+                Some(reading) => {
+                    if previous_is_reading {
+                        res.push(' ');
+                    }
+
+                    res.push_str(&reading);
+                    previous_is_reading = true;
+                }
+                None => {
+                    res.push_str(token.text);
+                    previous_is_reading = false;
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// Annotator choose the reading of each word of a text. The text is split with the longest headwords of the
+/// dictionary from left to right. Hence the reading of a polyphone e.g: 行 [xing2] or [hang2] is taken from the
+/// longest headword which contains it e.g: 银行 -> hang2. When the headword has several readings the most common
+/// one is used. The rules of the user override both
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::annotation::{Annotator, PinyinFormat};
+/// use dodo_zh::cedict::Dictionary;
+/// use dodo_zh::polyphone::{PolyphoneRules, Resolution};
+/// use dodo_zh::variant::KeyVariant;
+///
+/// let dictionary = Dictionary::from_str("行 行 [xing2] /to walk/\n行 行 [hang2] /row/\n银行 银行 [yin2 hang2] /bank/", KeyVariant::Simplified).unwrap();
+/// let rules = PolyphoneRules::from_reader("行行 hang2 hang2".as_bytes()).unwrap();
+/// let annotation = Annotator::new(&dictionary).with_rules(&rules).annotate("银行 行行");
+///
+/// assert_eq!(annotation.to_pinyin(PinyinFormat::ToneNumber).unwrap(), "yin2 hang2 hang2 hang2");
+/// assert_eq!(annotation.ambiguities()[0].resolution, Resolution::Word);
+/// assert_eq!(annotation.ambiguities()[1].resolution, Resolution::Rule);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Annotator<'d, 'r> {
+    dictionary: &'d Dictionary,
+    rules: Option<&'r PolyphoneRules>,
+}
+
+impl<'d, 'r> Annotator<'d, 'r> {
+    /// Create a new annotator without any rules
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary` - &'d Dictionary
+    pub fn new(dictionary: &'d Dictionary) -> Self {
+        Self {
+            dictionary,
+            rules: None,
+        }
+    }
+
+    /// Override the readings found with the dictionary by the rules
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `rules` - &'r PolyphoneRules
+    pub fn with_rules(mut self, rules: &'r PolyphoneRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Split a text into words and annotate each word with its reading
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &'t str
+    pub fn annotate<'t>(&self, text: &'t str) -> Annotation<'d, 't> {
+        let index = self.dictionary.polyphone_index();
+
+        // The longest headwords are matched so that a polyphone is read as in the longest word containing it
+        let mut tokens = Vec::new();
+        let mut resolutions = Vec::new();
+        let mut entries = Vec::new();
+        for token in Segmenter::new(self.dictionary)
+            .with_mode(SegmentMode::MaximumMatching)
+            .segment(text)
+        {
+            let items = token.items.clone();
+            let (token, resolution) = select_reading(token, index);
+            tokens.push(token);
+            resolutions.push(resolution);
+            entries.push(items);
+        }
+
+        // Byte offset of the characters whose reading is set by a rule
+        let mut overridden = HashSet::new();
+        let mut unapplied_rules = Vec::new();
+        for (span, rule) in self
+            .rules
+            .map(|rules| rules.matches(text))
+            .unwrap_or_default()
+        {
+            // The token, the index in the token and the byte offset of each character of the phrase. The rule is only
+            // applied when every character has a syllable which can be replaced
+            let targets = text[span.clone()]
+                .char_indices()
+                .map(|(offset, _)| {
+                    let position = span.start + offset;
+                    let idx = tokens.partition_point(|token| token.span.end <= position);
+                    let token = tokens.get(idx)?;
+                    // A character missing from the dictionary may not have a reading yet
+                    let missing_reading =
+                        token.kind == TokenKind::Unknown && token.pinyin_tone_number.is_empty();
+
+                    (token.is_aligned() || missing_reading).then(|| {
+                        let character = text[token.span.start..position].chars().count();
+                        (idx, character, position)
+                    })
+                })
+                .collect::<Option<Vec<_>>>();
+
+            let Some(targets) = targets else {
+                unapplied_rules.push(UnappliedRule {
+                    phrase: rule.phrase.clone(),
+                    span,
+                });
+                continue;
+            };
+
+            for ((idx, character, position), pinyin) in
+                targets.into_iter().zip(&rule.pinyin_tone_number)
+            {
+                let token = &mut tokens[idx];
+                if token.pinyin_tone_number.is_empty() {
+                    token.pinyin_tone_number.push(String::new());
+                }
+
+                token.pinyin_tone_number[character] = pinyin.clone();
+                // The entry of the headword which is read as the rule is used. Otherwise the token has no entry
+                let reading = token.pinyin_tone_number.join(" ");
+                token.item = entries[idx]
+                    .iter()
+                    .find(|item| {
+                        item.pinyin_tone_number
+                            .join(" ")
+                            .eq_ignore_ascii_case(&reading)
+                    })
+                    .copied();
+                overridden.insert(position);
+            }
+        }
+
+        for (token, resolution) in tokens.iter_mut().zip(resolutions) {
+            if !token.is_aligned() {
+                continue;
+            }
+
+            let start = token.span.start;
+            token.ambiguities = token
+                .text
+                .char_indices()
+                .zip(&token.pinyin_tone_number)
+                .filter_map(|((offset, character), pinyin)| {
+                    let candidates = index.readings(character);
+                    if candidates.len() < 2 {
+                        return None;
+                    }
+
+                    let position = start + offset;
+                    let resolution = match overridden.contains(&position) {
+                        true => Resolution::Rule,
+                        false => resolution,
+                    };
+
+                    Some(Ambiguity {
+                        character,
+                        span: position..position + character.len_utf8(),
+                        reading: pinyin.clone(),
+                        candidates: candidates
+                            .iter()
+                            .map(|reading| reading.pinyin.clone())
+                            .collect(),
+                        resolution,
+                    })
+                })
+                .collect();
+        }
+
+        Annotation {
+            tokens,
+            unapplied_rules,
+        }
+    }
+}
+
+/// Choose the entry of a token. The entry whose readings are the most common is used when the entries of the
+/// headword have different readings e.g: 了 [le5] and 了 [liao3]. A character which is not a headword of the
+/// dictionary get its most common reading among the words of the dictionary
+///
+/// # Arguments
+///
+/// * `token` - Token<'d, 't>
+/// * `index` - &PolyphoneIndex
+fn select_reading<'d, 't>(
+    token: Token<'d, 't>,
+    index: &PolyphoneIndex,
+) -> (AnnotatedToken<'d, 't>, Resolution) {
+    let mut readings = token
+        .items
+        .iter()
+        .map(|item| item.pinyin_tone_number.join(" ").to_lowercase())
+        .collect::<Vec<_>>();
+    readings.sort_unstable();
+    readings.dedup();
+
+    let (item, pinyin_tone_number, resolution) = match token.items.as_slice() {
+        [] => {
+            let mut characters = token.text.chars();
+            match (token.kind, characters.next(), characters.next()) {
+                (TokenKind::Unknown, Some(character), None) => (
+                    None,
+                    index
+                        .readings(character)
+                        .first()
+                        .map(|reading| vec![reading.pinyin.clone()])
+                        .unwrap_or_default(),
+                    Resolution::Frequency,
+                ),
+                _ => (None, Vec::new(), Resolution::Word),
+            }
+        }
+        [first, ..] if readings.len() == 1 => (
+            Some(*first),
+            first.pinyin_tone_number.clone(),
+            Resolution::Word,
+        ),
+        items => {
+            let mut best = (f64::NEG_INFINITY, items[0]);
+            for item in items {
+                let weight = token
+                    .text
+                    .chars()
+                    .zip(&item.pinyin_tone_number)
+                    .map(|(character, pinyin)| index.weight(character, pinyin))
+                    .sum::<f64>();
+                // Entries of the same weight keep the order of the cedict file
+                if weight > best.0 {
+                    best = (weight, item);
+                }
+            }
+
+            (
+                Some(best.1),
+                best.1.pinyin_tone_number.clone(),
+                Resolution::Frequency,
+            )
+        }
+    };
+
+    let annotated = AnnotatedToken {
+        text: token.text,
+        span: token.span,
+        kind: token.kind,
+        item,
+        pinyin_tone_number,
+        ambiguities: Vec::new(),
+    };
+
+    (annotated, resolution)
+}
+
+impl Dictionary {
+    /// Split a text into words and annotate each word with the pinyin of its entry. The readings of the
+    /// polyphones are chosen with the words of the dictionary and the frequencies
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &'t str
+    pub fn annotate<'t>(&self, text: &'t str) -> Annotation<'_, 't> {
+        Annotator::new(self).annotate(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+
+    #[test]
+    fn expect_to_annotate_sentence_in_every_format() {
+        let dictionary = Dictionary::from_str(
+            "我 我 [wo3] /I/\n喜歡 喜欢 [xi3 huan5] /to like/\n中國 中国 [Zhong1 guo2] /China/",
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let annotation = dictionary.annotate("我喜欢中国。");
+        let words = annotation
+            .tokens
+            .iter()
+            .map(|token| (token.text, token.item.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            vec![("我", true), ("喜欢", true), ("中国", true), ("。", false)]
+        );
+
+        assert_eq!(
+            annotation.to_pinyin(PinyinFormat::ToneNumber).unwrap(),
+            "wo3 xi3 huan5 Zhong1 guo2。"
+        );
+        assert_eq!(
+            annotation.to_pinyin(PinyinFormat::ToneMark).unwrap(),
+            "wo\u{30c} xi\u{30c} huan Zho\u{304}ng guo\u{301}。"
+        );
+        assert_eq!(
+            annotation.tokens[0].pinyin(PinyinFormat::Zhuyin).unwrap(),
+            Some("ㄨㄛ\u{30c}".to_string())
+        );
+        assert_eq!(
+            annotation.tokens[1]
+                .pinyin(PinyinFormat::WadeGiles)
+                .unwrap(),
+            Some("hsi3 huan5".to_string())
+        );
+    }
+
+    #[test]
+    fn expect_to_keep_unknown_characters() {
+        let dictionary =
+            Dictionary::from_str("好 好 [hao3] /good/", KeyVariant::Simplified).unwrap();
+
+        let annotation = dictionary.annotate("很好 ok");
+        assert_eq!(
+            annotation.to_pinyin(PinyinFormat::ToneMark).unwrap(),
+            "很ha\u{30c}o ok"
+        );
+    }
+
+    #[test]
+    fn expect_to_disambiguate_polyphones() {
+        let dictionary = Dictionary::from_str(
+            "了 了 [le5] /particle/\n了 了 [liao3] /to finish/\n了解 了解 [liao3 jie3] /to understand/\n為了 为了 [wei4 le5] /for/\n除了 除了 [chu2 le5] /besides/\n行 行 [xing2] /to walk/\n行 行 [hang2] /row/\n銀行 银行 [yin2 hang2] /bank/\n不行 不行 [bu4 xing2] /no way/",
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let annotation = dictionary.annotate("银行不行了");
+        assert_eq!(
+            annotation.to_pinyin(PinyinFormat::ToneNumber).unwrap(),
+            "yin2 hang2 bu4 xing2 le5"
+        );
+
+        let ambiguities = annotation
+            .ambiguities()
+            .into_iter()
+            .map(|ambiguity| {
+                (
+                    ambiguity.character,
+                    ambiguity.reading.as_str(),
+                    ambiguity.resolution,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ambiguities,
+            vec![
+                ('行', "hang2", Resolution::Word),
+                ('行', "xing2", Resolution::Word),
+                ('了', "le5", Resolution::Frequency),
+            ]
+        );
+        assert_eq!(annotation.ambiguities()[2].candidates, vec!["le5", "liao3"]);
+
+        let rules = PolyphoneRules::from_reader("行了 xing2 liao3".as_bytes()).unwrap();
+        let annotation = Annotator::new(&dictionary)
+            .with_rules(&rules)
+            .annotate("不行了");
+        assert_eq!(
+            annotation.to_pinyin(PinyinFormat::ToneNumber).unwrap(),
+            "bu4 xing2 liao3"
+        );
+        assert_eq!(annotation.ambiguities()[0].resolution, Resolution::Rule);
+        assert_eq!(annotation.ambiguities()[1].resolution, Resolution::Rule);
+        assert_eq!(
+            annotation.tokens[1].item.unwrap().pinyin_tone_number,
+            vec!["liao3"]
+        );
+        assert!(annotation.unapplied_rules.is_empty());
+    }
+
+    #[test]
+    fn expect_to_report_unapplied_rules() {
+        let dictionary = Dictionary::from_str(
+            "好玩兒 好玩儿 [hao3 wanr2] /fun/\n好 好 [hao3] /good/\n好 好 [hao4] /to be fond of/",
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+        let rules = PolyphoneRules::from_reader("玩儿 wan2 r5\n好 hao4".as_bytes()).unwrap();
+
+        let annotation = Annotator::new(&dictionary)
+            .with_rules(&rules)
+            .annotate("好玩儿");
+        assert_eq!(
+            annotation.unapplied_rules,
+            vec![
+                UnappliedRule {
+                    phrase: "好".to_string(),
+                    span: 0..3
+                },
+                UnappliedRule {
+                    phrase: "玩儿".to_string(),
+                    span: 3..9
+                }
+            ]
+        );
+        assert_eq!(
+            annotation.tokens[0].pinyin_tone_number,
+            vec!["hao3", "wanr2"]
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use crate::error::Error;
+use crc32fast::Hasher;
+use flate2::bufread::{DeflateDecoder, MultiGzDecoder};
+use lzma_rust2::XzReader;
+use std::fmt;
+use std::io::{self, BufReader, Chain, Cursor, Read, Take};
+
+// Constant
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_DATA_DESCRIPTOR_MAGIC: &[u8] = b"PK\x07\x08";
+// Number of bytes needed to detect every compression format
+const MAGIC_LENGTH: usize = 6;
+// Length of the local file header of a zip entry without the file name and the extra field
+const ZIP_HEADER_LENGTH: usize = 30;
+const ZIP_STORED: u16 = 0;
+const ZIP_DEFLATED: u16 = 8;
+// The sizes are written after the data when this flag is set
+const ZIP_DATA_DESCRIPTOR_FLAG: u16 = 1 << 3;
+
+/// Compression is the compression format of a dictionary which is detected from its magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Xz,
+    Zip,
+}
+
+impl Compression {
+    /// Detect the compression format from the first bytes of a file
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - &[u8]
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(XZ_MAGIC) {
+            Compression::Xz
+        } else if bytes.starts_with(ZIP_MAGIC) {
+            Compression::Zip
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// DecompressReader decompress a dictionary while it's being read. The compression format is detected
+/// from the magic bytes. A zip archive must have a single entry e.g: the cedict_ts.u8 file of the
+/// MDBG distribution. The CRC-32 of the entry is checked once it has been read. Plain text is read as it is.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::{Compression, DecompressReader};
+/// use std::io::Read;
+///
+/// let mut reader = DecompressReader::new("她 她 [ta1] /she/".as_bytes()).unwrap();
+/// assert_eq!(reader.compression(), Compression::None);
+///
+/// let mut content = String::new();
+/// reader.read_to_string(&mut content).unwrap();
+/// ```
+pub struct DecompressReader<R: Read> {
+    inner: Inner<R>,
+    compression: Compression,
+    file_name: Option<String>,
+}
+
+// The magic bytes which have been read to detect the compression are put back in front of the reader
+type Source<R> = BufReader<Chain<Cursor<Vec<u8>>, R>>;
+
+enum Inner<R: Read> {
+    Plain(Source<R>),
+    Gzip(Box<MultiGzDecoder<Source<R>>>),
+    Xz(Box<XzReader<Source<R>>>),
+    Zip(Box<ZipReader<R>>),
+}
+
+impl<R: Read> DecompressReader<R> {
+    /// Create a new reader which decompress the content of the given reader
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        // A single read may return less bytes than the magic bytes e.g: pipes or sockets
+        let mut magic = Vec::with_capacity(MAGIC_LENGTH);
+        (&mut reader)
+            .take(MAGIC_LENGTH as u64)
+            .read_to_end(&mut magic)?;
+
+        let compression = Compression::detect(&magic);
+        let mut reader = BufReader::new(Cursor::new(magic).chain(reader));
+
+        let mut file_name = None;
+        let inner = match compression {
+            Compression::None => Inner::Plain(reader),
+            Compression::Gzip => Inner::Gzip(Box::new(MultiGzDecoder::new(reader))),
+            Compression::Xz => Inner::Xz(Box::new(XzReader::new(reader, true))),
+            Compression::Zip => {
+                let entry = ZipEntry::read(&mut reader)?;
+                file_name = Some(entry.file_name);
+
+                let data = match entry.method {
+                    ZIP_DEFLATED => ZipData::Deflated(Box::new(DeflateDecoder::new(reader))),
+                    ZIP_STORED if entry.flags & ZIP_DATA_DESCRIPTOR_FLAG == 0 => {
+                        ZipData::Stored(reader.take(entry.compressed_size))
+                    }
+                    method => {
+                        return Err(Error::Io(format!(
+                            "Unsupported compression method {method} for the zip entry"
+                        )));
+                    }
+                };
+
+                Inner::Zip(Box::new(ZipReader {
+                    data,
+                    hasher: Hasher::new(),
+                    flags: entry.flags,
+                    crc: entry.crc,
+                    finished: false,
+                }))
+            }
+        };
+
+        Ok(Self {
+            inner,
+            compression,
+            file_name,
+        })
+    }
+
+    /// Return the compression format which has been detected
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Return the name of the file which is read when the content is a zip archive
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+}
+
+impl<R: Read> fmt::Debug for DecompressReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecompressReader")
+            .field("compression", &self.compression)
+            .field("file_name", &self.file_name)
+            .finish()
+    }
+}
+
+impl<R: Read> Read for DecompressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.read(buf),
+            Inner::Gzip(reader) => reader.read(buf),
+            Inner::Xz(reader) => reader.read(buf),
+            Inner::Zip(reader) => reader.read(buf),
+        }
+    }
+}
+
+/// ZipData is the data of the entry of a zip archive
+enum ZipData<R: Read> {
+    Stored(Take<Source<R>>),
+    Deflated(Box<DeflateDecoder<Source<R>>>),
+}
+
+/// ZipReader read the entry of a zip archive and check its CRC-32 once the entry has been read
+struct ZipReader<R: Read> {
+    data: ZipData<R>,
+    hasher: Hasher,
+    flags: u16,
+    crc: u32,
+    finished: bool,
+}
+
+impl<R: Read> ZipReader<R> {
+    /// Check the CRC-32 of the entry and that the archive does not have an other entry. The CRC-32 is
+    /// read from the data descriptor which follow the data when the local file header does not have it
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    fn finish(&mut self) -> io::Result<()> {
+        self.finished = true;
+
+        let reader = match &mut self.data {
+            ZipData::Stored(reader) => reader.get_mut(),
+            ZipData::Deflated(reader) => reader.get_mut(),
+        };
+
+        let mut crc = self.crc;
+        if self.flags & ZIP_DATA_DESCRIPTOR_FLAG != 0 {
+            let mut descriptor = [0_u8; 4];
+            reader.read_exact(&mut descriptor)?;
+            // The signature of the data descriptor is optional
+            if descriptor == ZIP_DATA_DESCRIPTOR_MAGIC {
+                reader.read_exact(&mut descriptor)?;
+            }
+
+            crc = u32::from_le_bytes(descriptor);
+            // Skip the compressed and uncompressed sizes
+            io::copy(&mut reader.take(8), &mut io::sink())?;
+        }
+
+        let actual = std::mem::take(&mut self.hasher).finalize();
+        if actual != crc {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The CRC-32 of the zip entry is {actual:08x} but {crc:08x} was expected"),
+            ));
+        }
+
+        // The central directory follow the entry when the archive has a single entry
+        let mut signature = Vec::with_capacity(ZIP_MAGIC.len());
+        reader
+            .take(ZIP_MAGIC.len() as u64)
+            .read_to_end(&mut signature)?;
+        if signature == ZIP_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The zip archive has more than one entry",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for ZipReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = match &mut self.data {
+            ZipData::Stored(reader) => reader.read(buf)?,
+            ZipData::Deflated(reader) => reader.read(buf)?,
+        };
+
+        self.hasher.update(&buf[..read]);
+        if read == 0 && !buf.is_empty() && !self.finished {
+            self.finish()?;
+        }
+
+        Ok(read)
+    }
+}
+
+/// ZipEntry is the local file header of the first entry of a zip archive
+struct ZipEntry {
+    flags: u16,
+    method: u16,
+    crc: u32,
+    compressed_size: u64,
+    file_name: String,
+}
+
+impl ZipEntry {
+    /// Read the local file header. The reader is positioned on the data of the entry afterward
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - &mut R
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut header = [0_u8; ZIP_HEADER_LENGTH];
+        reader.read_exact(&mut header)?;
+
+        let u16_at = |offset: usize| u16::from_le_bytes([header[offset], header[offset + 1]]);
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes([
+                header[offset],
+                header[offset + 1],
+                header[offset + 2],
+                header[offset + 3],
+            ])
+        };
+
+        let mut file_name = vec![0_u8; u16_at(26) as usize];
+        reader.read_exact(&mut file_name)?;
+
+        let mut extra = vec![0_u8; u16_at(28) as usize];
+        reader.read_exact(&mut extra)?;
+
+        Ok(Self {
+            flags: u16_at(6),
+            method: u16_at(8),
+            crc: u32_at(14),
+            compressed_size: u64::from(u32_at(18)),
+            file_name: String::from_utf8_lossy(&file_name).to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+    use std::fs::File;
+    use std::path::PathBuf;
+
+    #[test]
+    fn expect_to_decompress_archives() {
+        let expected = std::fs::read_to_string("../static/cedict_sample_ts.u8").unwrap();
+
+        for (path, compression) in [
+            ("../static/cedict_sample_ts.u8.gz", Compression::Gzip),
+            ("../static/cedict_sample_ts.u8.xz", Compression::Xz),
+            ("../static/cedict_sample_ts.zip", Compression::Zip),
+            ("../static/cedict_sample_ts.u8", Compression::None),
+        ] {
+            let mut reader = DecompressReader::new(File::open(path).unwrap()).unwrap();
+            assert_eq!(reader.compression(), compression);
+
+            let mut content = String::new();
+            reader.read_to_string(&mut content).unwrap();
+            assert_eq!(content, expected);
+        }
+    }
+
+    #[test]
+    fn expect_to_load_dictionary_from_archive() {
+        let dictionary = crate::load_cedict_dictionary(
+            PathBuf::from("../static/cedict_sample_ts.zip"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        assert_eq!(Some(dictionary.len()), dictionary.metadata.entries);
+        assert_eq!(dictionary.get("了").len(), 2);
+    }
+
+    // Reader which return a single byte on each read like a slow pipe
+    struct ByteReader<R: Read>(R);
+
+    impl<R: Read> Read for ByteReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let length = buf.len().min(1);
+            self.0.read(&mut buf[..length])
+        }
+    }
+
+    // Build a local file header and the stored data of a zip entry
+    fn stored_entry(name: &str, content: &[u8], crc: u32) -> Vec<u8> {
+        let mut entry = ZIP_MAGIC.to_vec();
+        entry.extend([20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        entry.extend(crc.to_le_bytes());
+        entry.extend((content.len() as u32).to_le_bytes());
+        entry.extend((content.len() as u32).to_le_bytes());
+        entry.extend((name.len() as u16).to_le_bytes());
+        entry.extend([0, 0]);
+        entry.extend(name.as_bytes());
+        entry.extend(content);
+
+        entry
+    }
+
+    #[test]
+    fn expect_to_detect_compression_on_short_reads() {
+        let expected = std::fs::read_to_string("../static/cedict_sample_ts.u8").unwrap();
+        let file = File::open("../static/cedict_sample_ts.u8.gz").unwrap();
+
+        let mut reader = DecompressReader::new(ByteReader(file)).unwrap();
+        assert_eq!(reader.compression(), Compression::Gzip);
+
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        assert_eq!(content, expected);
+    }
+
+    #[test]
+    fn expect_to_reject_invalid_zip_archives() {
+        let content = "她 她 [ta1] /she/\n".as_bytes();
+        let crc = crc32fast::hash(content);
+
+        let mut archive = stored_entry("cedict_ts.u8", content, crc);
+        archive.extend(b"PK\x01\x02");
+        let mut reader = DecompressReader::new(archive.as_slice()).unwrap();
+        assert_eq!(reader.file_name(), Some("cedict_ts.u8"));
+        assert!(reader.read_to_end(&mut Vec::new()).is_ok());
+
+        let corrupted = stored_entry("cedict_ts.u8", content, crc ^ 1);
+        let mut reader = DecompressReader::new(corrupted.as_slice()).unwrap();
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+
+        let mut archive = stored_entry("cedict_ts.u8", content, crc);
+        archive.extend(stored_entry(
+            "readme.txt",
+            b"readme",
+            crc32fast::hash(b"readme"),
+        ));
+        let mut reader = DecompressReader::new(archive.as_slice()).unwrap();
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::fmt;
+
+/// DiagnosticKind is the kind of issue found while loading a cedict file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagnosticKind {
+    /// The line could not be parsed and has been skipped
+    Parse,
+    /// The number of entries declared by the `#! entries=` header does not match the parsed entries
+    EntriesHeader,
+}
+
+/// Diagnostic describe a line of the cedict file which could not be loaded
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub line_number: usize,
+    pub line: String,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+/// LineError is the reason why a cedict line could not be parsed with the column where the error happened
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LineError {
+    pub column: usize,
+    pub reason: String,
+}
+
+impl LineError {
+    /// Create a new line error. The column is computed from the byte offset as the number of characters
+    /// preceding the offset starting at 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - &str
+    /// * `offset` - usize
+    /// * `reason` - S
+    pub(crate) fn new<S: AsRef<str>>(line: &str, offset: usize, reason: S) -> Self {
+        let column = line.get(..offset).unwrap_or(line).chars().count() + 1;
+
+        Self {
+            column,
+            reason: reason.as_ref().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(
+                f,
+                "line {}, column {column}: {} ({})",
+                self.line_number, self.reason, self.line
+            ),
+            None => write!(
+                f,
+                "line {}: {} ({})",
+                self.line_number, self.reason, self.line
+            ),
+        }
+    }
+}
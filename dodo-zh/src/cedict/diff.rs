@@ -0,0 +1,260 @@
+use super::{Dictionary, Item};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// DictionaryDiff is the list of the changes between two dictionaries. Entries are identified by their
+/// traditional and simplified characters. Hence a change of pinyin is reported as a modification.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DictionaryDiff<'a> {
+    pub added: Vec<&'a Item>,
+    pub removed: Vec<&'a Item>,
+    pub modified: Vec<ModifiedEntry<'a>>,
+}
+
+/// ModifiedEntry is an entry which exist in both dictionaries with different fields
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifiedEntry<'a> {
+    pub old: &'a Item,
+    pub new: &'a Item,
+    pub changes: Vec<FieldChange>,
+}
+
+/// FieldChange is a change of a field of an entry. Translations are compared one by one
+/// regardless of their order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldChange {
+    Pinyin {
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    Jyutping {
+        old: Option<Vec<String>>,
+        new: Option<Vec<String>>,
+    },
+    TranslationAdded {
+        translation: String,
+    },
+    TranslationRemoved {
+        translation: String,
+    },
+}
+
+impl Dictionary {
+    /// Compare the dictionary with a newer version of the dictionary e.g: two releases of cedict.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `new` - &Dictionary
+    pub fn diff<'a>(&'a self, new: &'a Dictionary) -> DictionaryDiff<'a> {
+        let mut old_entries: HashMap<(&str, &str), Vec<&Item>> = HashMap::new();
+        for item in &self.items {
+            old_entries.entry(entry_key(item)).or_default().push(item);
+        }
+
+        let mut new_entries: Vec<((&str, &str), Vec<&Item>)> = Vec::new();
+        let mut new_positions: HashMap<(&str, &str), usize> = HashMap::new();
+        for item in &new.items {
+            let position = *new_positions.entry(entry_key(item)).or_insert_with(|| {
+                new_entries.push((entry_key(item), Vec::new()));
+                new_entries.len() - 1
+            });
+
+            new_entries[position].1.push(item);
+        }
+
+        let mut diff = DictionaryDiff::default();
+        for (key, new_items) in new_entries {
+            let mut old_items = old_entries.remove(&key).unwrap_or_default();
+
+            // Entries which share the same pinyin are paired first. The remaining entries of the headword
+            // are paired in the order of the files e.g: an entry where only the pinyin has been fixed.
+            let mut unpaired = Vec::new();
+            for new_item in new_items {
+                match old_items
+                    .iter()
+                    .position(|old| old.pinyin_tone_number == new_item.pinyin_tone_number)
+                {
+                    Some(idx) => diff.push_pair(old_items.remove(idx), new_item),
+                    None => unpaired.push(new_item),
+                }
+            }
+
+            let mut old_items = old_items.into_iter();
+            for new_item in unpaired {
+                match old_items.next() {
+                    Some(old_item) => diff.push_pair(old_item, new_item),
+                    None => diff.added.push(new_item),
+                }
+            }
+
+            diff.removed.extend(old_items);
+        }
+
+        // The entries of the headwords which do not exist anymore are listed in the order of the old dictionary
+        diff.removed.extend(
+            self.items
+                .iter()
+                .filter(|item| old_entries.contains_key(&entry_key(item))),
+        );
+
+        diff
+    }
+}
+
+impl<'a> DictionaryDiff<'a> {
+    /// Check whether the dictionaries are the same
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Compare two entries and add them to the modified entries when their fields differ
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `old` - &Item
+    /// * `new` - &Item
+    fn push_pair(&mut self, old: &'a Item, new: &'a Item) {
+        let mut changes = Vec::new();
+        if old.pinyin_tone_number != new.pinyin_tone_number {
+            changes.push(FieldChange::Pinyin {
+                old: old.pinyin_tone_number.clone(),
+                new: new.pinyin_tone_number.clone(),
+            });
+        }
+
+        if old.jyutping != new.jyutping {
+            changes.push(FieldChange::Jyutping {
+                old: old.jyutping.clone(),
+                new: new.jyutping.clone(),
+            });
+        }
+
+        changes.extend(
+            old.translations
+                .iter()
+                .filter(|translation| !new.translations.contains(translation))
+                .map(|translation| FieldChange::TranslationRemoved {
+                    translation: translation.to_owned(),
+                }),
+        );
+
+        changes.extend(
+            new.translations
+                .iter()
+                .filter(|translation| !old.translations.contains(translation))
+                .map(|translation| FieldChange::TranslationAdded {
+                    translation: translation.to_owned(),
+                }),
+        );
+
+        if !changes.is_empty() {
+            self.modified.push(ModifiedEntry { old, new, changes });
+        }
+    }
+}
+
+/// Get the key which identify an entry across dictionaries
+///
+/// # Arguments
+///
+/// * `item` - &Item
+fn entry_key(item: &Item) -> (&str, &str) {
+    (&item.traditional_character, &item.simplified_character)
+}
+
+impl fmt::Display for DictionaryDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.added {
+            writeln!(f, "+ {item}")?;
+        }
+
+        for item in &self.removed {
+            writeln!(f, "- {item}")?;
+        }
+
+        for entry in &self.modified {
+            writeln!(
+                f,
+                "~ {} {} [{}]",
+                entry.old.traditional_character,
+                entry.old.simplified_character,
+                entry.old.pinyin_tone_number.join(" ")
+            )?;
+
+            for change in &entry.changes {
+                match change {
+                    FieldChange::Pinyin { old, new } => {
+                        writeln!(f, "    pinyin: {} -> {}", old.join(" "), new.join(" "))?
+                    }
+                    FieldChange::Jyutping { old, new } => writeln!(
+                        f,
+                        "    jyutping: {} -> {}",
+                        old.as_deref().unwrap_or_default().join(" "),
+                        new.as_deref().unwrap_or_default().join(" ")
+                    )?,
+                    FieldChange::TranslationAdded { translation } => {
+                        writeln!(f, "    + {translation}")?
+                    }
+                    FieldChange::TranslationRemoved { translation } => {
+                        writeln!(f, "    - {translation}")?
+                    }
+                }
+            }
+        }
+
+        write!(
+            f,
+            "{} added, {} removed, {} modified",
+            self.added.len(),
+            self.removed.len(),
+            self.modified.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+
+    #[test]
+    fn expect_to_diff_dictionaries() {
+        let old = Dictionary::from_str(
+            "了 了 [le5] /completed action marker/\n了 了 [liao3] /to finish/\n北大 北大 [Bei3 da4] /Peking University/\n她 她 [ta1] /she/",
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+        let new = Dictionary::from_str(
+            "了 了 [le5] /completed action marker/\n了 了 [liao3] /to finish; to end/\n北大 北大 [Bei3 Da4] /Peking University/\n友 友 [you3] /friend/",
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].traditional_character, "友");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].traditional_character, "她");
+        assert_eq!(diff.modified.len(), 2);
+        assert_eq!(
+            diff.modified[0].changes,
+            vec![
+                FieldChange::TranslationRemoved {
+                    translation: "to finish".to_string()
+                },
+                FieldChange::TranslationAdded {
+                    translation: "to finish; to end".to_string()
+                }
+            ]
+        );
+        assert!(matches!(
+            diff.modified[1].changes[0],
+            FieldChange::Pinyin { .. }
+        ));
+        assert!(old.diff(&old).is_empty());
+    }
+}
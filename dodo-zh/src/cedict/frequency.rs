@@ -0,0 +1,330 @@
+use super::index::{PinyinMatch, TranslationMatch};
+use super::{Dictionary, Item};
+use crate::error::Error;
+use encoding_rs::GB18030;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// Constant
+const PER_MILLION: f64 = 1_000_000.0;
+
+/// Frequency is how common a word is in a corpus. The most common word has the rank 1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Frequency {
+    pub rank: usize,
+    pub per_million: f64,
+}
+
+/// FrequencyList is a list of words with their number of occurrences in a corpus. The list may be
+/// SUBTLEX-CH, BCC or any file with the format `word<TAB>count`. The lines which do not have a count
+/// in their second column e.g: the header of SUBTLEX-CH are skipped. The count per million of a word is
+/// read from the column whose header contains `million` e.g: `W/million` when the list has one.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::FrequencyList;
+///
+/// let list = FrequencyList::from_reader("的\t800\n我\t150\n你\t50".as_bytes()).unwrap();
+///
+/// assert_eq!(list.get("我").unwrap().rank, 2);
+/// assert_eq!(list.get("你").unwrap().per_million, 50_000.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyList {
+    words: HashMap<String, Frequency>,
+}
+
+impl FrequencyList {
+    /// Load a frequency list from the file path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - &PathBuf
+    pub fn new(path: &PathBuf) -> Result<FrequencyList, Error> {
+        FrequencyList::from_reader(File::open(path)?)
+    }
+
+    /// Load a frequency list from any reader. A word which is listed several times e.g: once per part of
+    /// speech has the sum of its counts. The list may be encoded in UTF-8 or in GBK like SUBTLEX-CH.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<FrequencyList, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let mut counts: Vec<(String, f64, Option<f64>)> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut per_million_column = None;
+
+        for line in decode(&bytes).lines() {
+            let columns = split_columns(line);
+            let Some((word, count, per_million)) = parse_columns(&columns, per_million_column)
+            else {
+                // The header of the columns e.g: `Word WCount W/million` of SUBTLEX-CH
+                per_million_column = per_million_column.or_else(|| {
+                    columns
+                        .iter()
+                        .position(|column| column.to_lowercase().contains("million"))
+                });
+                continue;
+            };
+
+            match positions.get(word) {
+                Some(position) => {
+                    let (_, total, total_per_million) = &mut counts[*position];
+                    *total += count;
+                    *total_per_million = total_per_million.zip(per_million).map(|(a, b)| a + b);
+                }
+                None => {
+                    positions.insert(word.to_string(), counts.len());
+                    counts.push((word.to_string(), count, per_million));
+                }
+            }
+        }
+
+        let total = counts.iter().map(|(_, count, _)| count).sum::<f64>();
+        // The sort is stable hence words with the same count keep the order of the file
+        counts.sort_by(|(_, a, _), (_, b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+        let words = counts
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (word, count, per_million))| {
+                let frequency = Frequency {
+                    rank: idx + 1,
+                    per_million: per_million.unwrap_or(count / total * PER_MILLION),
+                };
+
+                (word, frequency)
+            })
+            .collect();
+
+        Ok(FrequencyList { words })
+    }
+
+    /// Get the frequency of a word
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `word` - S
+    pub fn get<S: AsRef<str>>(&self, word: S) -> Option<&Frequency> {
+        self.words.get(word.as_ref())
+    }
+
+    /// Return the number of words
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check whether the list has no words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Decode the content of a frequency list. A list which is not encoded in UTF-8 is decoded as GB18030
+/// which is a superset of GBK. Invalid bytes are replaced instead of failing the loading
+///
+/// # Arguments
+///
+/// * `bytes` - &[u8]
+fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(content) => Cow::Borrowed(content.strip_prefix('\u{feff}').unwrap_or(content)),
+        Err(_) => GB18030.decode(bytes).0,
+    }
+}
+
+/// Split a line of a frequency list into columns. The columns are separated by tabs or by spaces
+///
+/// # Arguments
+///
+/// * `line` - &str
+fn split_columns(line: &str) -> Vec<&str> {
+    match line.contains('\t') {
+        true => line.split('\t').map(str::trim).collect(),
+        false => line.split_whitespace().collect(),
+    }
+}
+
+/// Parse the word, the count and the count per million of a line of a frequency list
+///
+/// # Arguments
+///
+/// * `columns` - &[&str]
+/// * `per_million_column` - Option<usize>
+fn parse_columns<'a>(
+    columns: &[&'a str],
+    per_million_column: Option<usize>,
+) -> Option<(&'a str, f64, Option<f64>)> {
+    let parse = |column: &str| {
+        column
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value >= 0.0)
+    };
+
+    let word = *columns.first()?;
+    let count = parse(columns.get(1)?)?;
+    let per_million = per_million_column
+        .and_then(|idx| columns.get(idx))
+        .and_then(|column| parse(column));
+
+    (!word.is_empty()).then_some((word, count, per_million))
+}
+
+impl Dictionary {
+    /// Set the frequency of each entry from the frequency list. The frequency of the simplified headword
+    /// is used first as most lists are built from simplified corpora.
+    /// The readings of the polyphones are weighted again with the new frequencies
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `list` - &FrequencyList
+    pub fn apply_frequencies(&mut self, list: &FrequencyList) {
+        for item in &mut self.items {
+            item.frequency = list
+                .get(&item.simplified_character)
+                .or_else(|| list.get(&item.traditional_character))
+                .copied();
+        }
+
+        self.polyphone_index = OnceLock::new();
+    }
+}
+
+/// FrequencyRanked is implemented by the entries and by the search results so that they can be
+/// sorted or filtered by frequency
+pub trait FrequencyRanked {
+    /// Return the frequency of the entry
+    fn frequency(&self) -> Option<&Frequency>;
+}
+
+impl FrequencyRanked for Item {
+    fn frequency(&self) -> Option<&Frequency> {
+        self.frequency.as_ref()
+    }
+}
+
+impl<T: FrequencyRanked> FrequencyRanked for &T {
+    fn frequency(&self) -> Option<&Frequency> {
+        (*self).frequency()
+    }
+}
+
+impl FrequencyRanked for TranslationMatch<'_> {
+    fn frequency(&self) -> Option<&Frequency> {
+        self.item.frequency.as_ref()
+    }
+}
+
+impl FrequencyRanked for PinyinMatch<'_> {
+    fn frequency(&self) -> Option<&Frequency> {
+        self.item.frequency.as_ref()
+    }
+}
+
+/// Sort the entries from the most common to the least common. Entries without a frequency come last.
+/// The sort is stable hence entries with the same frequency keep their order e.g: the rank of a search
+///
+/// # Arguments
+///
+/// * `items` - &mut [T]
+pub fn sort_by_frequency<T: FrequencyRanked>(items: &mut [T]) {
+    items.sort_by_key(|item| {
+        item.frequency()
+            .map_or(usize::MAX, |frequency| frequency.rank)
+    });
+}
+
+/// Only keep the entries whose rank is lower or equal to the maximum rank e.g: the 5000 most common words
+///
+/// # Arguments
+///
+/// * `items` - Vec<T>
+/// * `max_rank` - usize
+pub fn filter_by_frequency<T: FrequencyRanked>(items: Vec<T>, max_rank: usize) -> Vec<T> {
+    items
+        .into_iter()
+        .filter(|item| {
+            item.frequency()
+                .is_some_and(|frequency| frequency.rank <= max_rank)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+
+    #[test]
+    fn expect_to_load_subtlex_list() {
+        let content = "Total word count: 1,000\nContext number: 10\nWord\tWCount\tW/million\tlogW\n的\t600\t600000\t2.77\n我\t300\t300000\t2.47\n了\t60\t60000\t1.77\n我\t40\t40000\t1.6";
+        let list = FrequencyList::from_reader(content.as_bytes()).unwrap();
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(
+            list.get("我"),
+            Some(&Frequency {
+                rank: 2,
+                per_million: 340_000.0
+            })
+        );
+        assert_eq!(list.get("了").unwrap().rank, 3);
+        assert!(list.get("Word").is_none());
+    }
+
+    #[test]
+    fn expect_to_load_gbk_list_with_per_million_column() {
+        // The list is an excerpt of a corpus hence the per million column does not match the counts of the list
+        let content = "Word\tWCount\tW/million\n的\t1690274\t50385.98\n我们\t323640\t9647.37";
+        let (bytes, _, _) = encoding_rs::GBK.encode(content);
+        assert!(std::str::from_utf8(&bytes).is_err());
+
+        let list = FrequencyList::from_reader(bytes.as_ref()).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            list.get("我们"),
+            Some(&Frequency {
+                rank: 2,
+                per_million: 9647.37
+            })
+        );
+        assert_eq!(list.get("的").unwrap().per_million, 50385.98);
+    }
+
+    #[test]
+    fn expect_to_sort_and_filter_by_frequency() {
+        let mut dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let list =
+            FrequencyList::from_reader("了\t900\n她\t500\n好\t300\n朋友\t100".as_bytes()).unwrap();
+        dictionary.apply_frequencies(&list);
+
+        let mut items = dictionary.items().iter().collect::<Vec<_>>();
+        sort_by_frequency(&mut items);
+        assert_eq!(items[0].simplified_character, "了");
+        assert_eq!(items[1].simplified_character, "了");
+        assert_eq!(items[2].simplified_character, "她");
+
+        let items = filter_by_frequency(items, 3);
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().all(|item| item.frequency.is_some()));
+    }
+}
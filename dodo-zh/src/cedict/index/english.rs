@@ -0,0 +1,249 @@
+use super::headword_length;
+use crate::cedict::Item;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+// Constant
+const CLAUSE_SEPARATOR: char = ';';
+const STOP_WORDS: [&str; 20] = [
+    "a", "an", "the", "to", "of", "for", "in", "on", "at", "and", "or", "with", "by", "as", "is",
+    "be", "sb", "sth", "one's", "etc",
+];
+
+/// EnglishIndex is an inverted index of the words used in the translations of the entries
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub(crate) struct EnglishIndex {
+    words: BTreeMap<String, Vec<Posting>>,
+}
+
+/// Posting is the position of a translation within the list of items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Posting {
+    item: usize,
+    translation: usize,
+}
+
+/// TranslationMatchKind is how well a translation match the query. The order of the variants
+/// is the order used to rank the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum TranslationMatchKind {
+    /// The whole translation is the query e.g: "friend"
+    Exact,
+    /// A clause of the translation is the query e.g: "close friend; intimate friend"
+    Clause,
+    /// Every words of the query are in the translation
+    Word,
+    /// The words of the query are the beginning of words in the translation e.g: "friendship"
+    Partial,
+}
+
+/// TranslationMatch is an item which match an english query with the best matching translation
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslationMatch<'a> {
+    pub item: &'a Item,
+    pub translation: &'a str,
+    pub kind: TranslationMatchKind,
+}
+
+impl EnglishIndex {
+    /// Build the english index from the list of items
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - &[Item]
+    pub(crate) fn new(items: &[Item]) -> Self {
+        let mut words: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+
+        for (item_idx, item) in items.iter().enumerate() {
+            for (translation_idx, translation) in item.translations.iter().enumerate() {
+                let posting = Posting {
+                    item: item_idx,
+                    translation: translation_idx,
+                };
+
+                for word in tokenize(translation) {
+                    let postings = words.entry(word).or_default();
+                    if postings.last() != Some(&posting) {
+                        postings.push(posting);
+                    }
+                }
+            }
+        }
+
+        Self { words }
+    }
+
+    /// Search the items which have a translation matching every words of the query. Stop words such as
+    /// "to" or "the" are ignored. Hence a query which only contains stop words returns no results.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `items` - &[Item]
+    /// * `query` - S
+    pub(crate) fn search<'a, S: AsRef<str>>(
+        &self,
+        items: &'a [Item],
+        query: S,
+    ) -> Vec<TranslationMatch<'a>> {
+        let query_words = tokenize(query.as_ref());
+        let normalized_query = normalize(query.as_ref());
+
+        // Each candidate keep whether every words of the query has been matched exactly.
+        let mut candidates: Option<HashMap<Posting, bool>> = None;
+        for query_word in &query_words {
+            let mut matched: HashMap<Posting, bool> = HashMap::new();
+            let words = self
+                .words
+                .range(query_word.to_owned()..)
+                .take_while(|(word, _)| word.starts_with(query_word.as_str()));
+
+            for (word, postings) in words {
+                for posting in postings {
+                    *matched.entry(*posting).or_default() |= word == query_word;
+                }
+            }
+
+            candidates = Some(match candidates {
+                None => matched,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(posting, exact)| {
+                        matched
+                            .get(&posting)
+                            .map(|other| (posting, exact && *other))
+                    })
+                    .collect(),
+            });
+        }
+
+        // Only keep the best translation of each item
+        let mut best = HashMap::new();
+        for (posting, exact_words) in candidates.unwrap_or_default() {
+            let item = &items[posting.item];
+            let translation = item.translations[posting.translation].as_str();
+
+            let kind = if normalize(translation) == normalized_query {
+                TranslationMatchKind::Exact
+            } else if translation
+                .split(CLAUSE_SEPARATOR)
+                .any(|clause| normalize(clause) == normalized_query)
+            {
+                TranslationMatchKind::Clause
+            } else if exact_words {
+                TranslationMatchKind::Word
+            } else {
+                TranslationMatchKind::Partial
+            };
+
+            let rank = (
+                kind,
+                translation.chars().count(),
+                headword_length(item),
+                posting.item,
+            );
+
+            best.entry(posting.item)
+                .and_modify(|current: &mut (_, _)| {
+                    if rank < current.0 {
+                        *current = (rank, translation);
+                    }
+                })
+                .or_insert((rank, translation));
+        }
+
+        let mut results = best.into_values().collect::<Vec<_>>();
+        results.sort_by_key(|(rank, _)| *rank);
+
+        results
+            .into_iter()
+            .map(|((kind, _, _, item_idx), translation)| TranslationMatch {
+                item: &items[item_idx],
+                translation,
+                kind,
+            })
+            .collect()
+    }
+}
+
+/// Split a text into a list of lowercase words without the stop words
+///
+/// # Arguments
+///
+/// * `text` - &str
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty() && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Normalize a text in order to compare it with the query. The content within parentheses is removed
+/// e.g: "(after a personal pronoun) hello" -> "hello"
+///
+/// # Arguments
+///
+/// * `text` - &str
+fn normalize(text: &str) -> String {
+    let mut depth = 0_usize;
+    let mut normalized = String::new();
+
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => normalized.push(c),
+            _ => {}
+        }
+    }
+
+    normalized
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cedict::Dictionary;
+    use crate::variant::KeyVariant;
+    use std::path::PathBuf;
+
+    #[test]
+    fn expect_to_rank_exact_translations_first() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let results = dictionary.search_translation("friend");
+        let headwords = results
+            .iter()
+            .map(|r| r.item.traditional_character.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(headwords, vec!["友", "朋友", "摯友"]);
+        assert_eq!(results[0].kind, TranslationMatchKind::Exact);
+        assert_eq!(results[2].kind, TranslationMatchKind::Word);
+    }
+
+    #[test]
+    fn expect_to_match_partial_words() {
+        let items = vec![
+            Item::try_from(
+                "友誼 友谊 [you3 yi4] /companionship; fellowship; friendship/".to_string(),
+            )
+            .unwrap(),
+        ];
+
+        let index = EnglishIndex::new(&items);
+        let results = index.search(&items, "the friend");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].kind, TranslationMatchKind::Partial);
+        assert!(index.search(&items, "to the").is_empty());
+    }
+}
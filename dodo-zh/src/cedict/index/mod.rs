@@ -0,0 +1,163 @@
+use super::{Dictionary, Item};
+use crate::variant::KeyVariant;
+use std::collections::BTreeMap;
+
+mod english;
+mod pinyin;
+mod polyphone;
+mod prefix;
+
+pub(crate) use english::EnglishIndex;
+pub use english::{TranslationMatch, TranslationMatchKind};
+pub(crate) use pinyin::PinyinIndex;
+pub use pinyin::{PinyinMatch, PinyinMatchKind};
+pub use polyphone::CharacterReading;
+pub(crate) use polyphone::PolyphoneIndex;
+pub use prefix::LongestMatch;
+pub(crate) use prefix::PrefixIndex;
+
+impl Dictionary {
+    /// Search the entries whose translations match the english query e.g: "friend". The results
+    /// are ranked so that exact translations come first and shorter translations are preferred.
+    /// The english index is built on the first search.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `query` - S
+    pub fn search_translation<S: AsRef<str>>(&self, query: S) -> Vec<TranslationMatch<'_>> {
+        self.english_index
+            .get_or_init(|| EnglishIndex::new(&self.items))
+            .search(&self.items, query)
+    }
+
+    /// Search the entries by their pinyin. The query may use tone numbers, tone marks or no tones at all
+    /// e.g: `ni3 hao3`, `nǐ hǎo`, `nihao`. Entries whose tones match the query come first.
+    /// The pinyin index is built on the first search.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `query` - S
+    pub fn search_pinyin<S: AsRef<str>>(&self, query: S) -> Vec<PinyinMatch<'_>> {
+        self.pinyin_index
+            .get_or_init(|| PinyinIndex::new(&self.items))
+            .search(&self.items, query)
+    }
+
+    /// Get every entries whose headword, written in the given variant, start with the prefix
+    /// e.g: 中国 -> 中国, 中国人. The prefix index is built on the first search.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `prefix` - S
+    /// * `variant` - &KeyVariant
+    pub fn prefix_search<S: AsRef<str>>(&self, prefix: S, variant: &KeyVariant) -> Vec<&Item> {
+        self.prefix_index(variant)
+            .prefix_search(&self.items, prefix)
+    }
+
+    /// Get the longest headword, written in the given variant, which is a prefix of the text
+    /// e.g: 中国人很好 -> 中国人. The prefix index is built on the first search.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &'t str
+    /// * `variant` - &KeyVariant
+    pub fn longest_prefix_match<'t>(
+        &self,
+        text: &'t str,
+        variant: &KeyVariant,
+    ) -> Option<LongestMatch<'_, 't>> {
+        self.prefix_index(variant)
+            .longest_prefix_match(&self.items, text)
+    }
+
+    /// Get every headwords which are a prefix of the text with their entries. The headwords of both variants
+    /// are matched when no variant is given. Shorter headwords come first and the entries are in the order
+    /// of the cedict file.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &str
+    /// * `variant` - Option<&KeyVariant>
+    pub(crate) fn prefix_matches(
+        &self,
+        text: &str,
+        variant: Option<&KeyVariant>,
+    ) -> Vec<(usize, Vec<&Item>)> {
+        let variants = match variant {
+            Some(variant) => vec![variant],
+            None => vec![&KeyVariant::Simplified, &KeyVariant::Traditional],
+        };
+
+        let mut matches: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for variant in variants {
+            for (end, indexes) in self.prefix_index(variant).prefix_matches(text) {
+                matches.entry(end).or_default().extend(indexes);
+            }
+        }
+
+        matches
+            .into_iter()
+            .map(|(end, mut indexes)| {
+                indexes.sort_unstable();
+                indexes.dedup();
+
+                (
+                    end,
+                    indexes.into_iter().map(|idx| &self.items[idx]).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the readings of a character from the most common to the least common one. A character with
+    /// several readings is a polyphone e.g: 行 -> xing2, hang2. The polyphone index is built on the first call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - char
+    pub fn readings(&self, character: char) -> &[CharacterReading] {
+        self.polyphone_index().readings(character)
+    }
+
+    /// Get the polyphone index
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub(crate) fn polyphone_index(&self) -> &PolyphoneIndex {
+        self.polyphone_index
+            .get_or_init(|| PolyphoneIndex::new(&self.items))
+    }
+
+    /// Get the prefix index of the given variant
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `variant` - &KeyVariant
+    pub(crate) fn prefix_index(&self, variant: &KeyVariant) -> &PrefixIndex {
+        let index = match variant {
+            KeyVariant::Simplified => &self.simplified_prefix_index,
+            KeyVariant::Traditional => &self.traditional_prefix_index,
+        };
+
+        index.get_or_init(|| PrefixIndex::new(&self.items, variant))
+    }
+}
+
+/// Get the number of characters of the headword of an item. It's used to prefer shorter words
+/// when two results have the same rank.
+///
+/// # Arguments
+///
+/// * `item` - &Item
+pub(crate) fn headword_length(item: &Item) -> usize {
+    item.simplified_character.chars().count()
+}
@@ -0,0 +1,186 @@
+use super::headword_length;
+use crate::cedict::Item;
+use crate::pinyin::numbers::{PinyinNumber, PinyinTones};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+// Constant
+const NEUTRAL_TONE: u32 = 5;
+
+/// PinyinIndex index the entries by their pinyin without the tones e.g: ni3 hao3 -> nihao
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub(crate) struct PinyinIndex {
+    syllables: HashMap<String, Vec<usize>>,
+}
+
+/// PinyinMatchKind is how well the tones of an entry match the query. The order of the variants
+/// is the order used to rank the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum PinyinMatchKind {
+    /// Every syllables of the entry have a tone in the query which match
+    Exact,
+    /// The tones of the query match but some syllables does not have any tones in the query e.g: `nihao`, `ni3 hao`
+    Toneless,
+    /// Some tones of the query does not match the tones of the entry
+    ToneMismatch,
+}
+
+/// PinyinMatch is an item which match a pinyin query
+#[derive(Debug, Clone, Serialize)]
+pub struct PinyinMatch<'a> {
+    pub item: &'a Item,
+    pub kind: PinyinMatchKind,
+}
+
+impl PinyinIndex {
+    /// Build the pinyin index from the list of items
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - &[Item]
+    pub(crate) fn new(items: &[Item]) -> Self {
+        let mut syllables: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (idx, item) in items.iter().enumerate() {
+            let (letters, _) = item_syllables(item);
+            syllables.entry(letters).or_default().push(idx);
+        }
+
+        Self { syllables }
+    }
+
+    /// Search the items matching the pinyin query. The query can be written with tone numbers, tone marks
+    /// or without tones, with or without spaces e.g: `ni3 hao3`, `nǐ hǎo`, `nihao`, `ni3hao3`.
+    /// Items whose tones match the query come first.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `items` - &[Item]
+    /// * `query` - S
+    pub(crate) fn search<'a, S: AsRef<str>>(
+        &self,
+        items: &'a [Item],
+        query: S,
+    ) -> Vec<PinyinMatch<'a>> {
+        let query = PinyinNumber(query.as_ref()).into_tones();
+        let letters = query.letters.iter().collect::<String>();
+        let Some(candidates) = self.syllables.get(&letters) else {
+            return Vec::new();
+        };
+
+        let mut results = candidates
+            .iter()
+            .map(|idx| {
+                let item = &items[*idx];
+                let kind = match_tones(&query, item);
+
+                ((kind, headword_length(item), *idx), item)
+            })
+            .collect::<Vec<_>>();
+
+        results.sort_by_key(|(rank, _)| *rank);
+
+        results
+            .into_iter()
+            .map(|((kind, _, _), item)| PinyinMatch { item, kind })
+            .collect()
+    }
+}
+
+/// Compare the tones of the query with the tones of the syllables of the item
+///
+/// # Arguments
+///
+/// * `query` - &PinyinTones
+/// * `item` - &Item
+fn match_tones(query: &PinyinTones, item: &Item) -> PinyinMatchKind {
+    let (_, syllables) = item_syllables(item);
+
+    // Each syllable is compared with the tones of the query which are written on its letters
+    let mut toneless = false;
+    for (range, syllable_tone) in syllables {
+        let mut tones = query
+            .tones
+            .iter()
+            .filter(|(position, _)| range.contains(position))
+            .peekable();
+
+        if tones.peek().is_none() {
+            toneless = true;
+        } else if tones.any(|(_, tone)| *tone != syllable_tone) {
+            return PinyinMatchKind::ToneMismatch;
+        }
+    }
+
+    match toneless {
+        true => PinyinMatchKind::Toneless,
+        false => PinyinMatchKind::Exact,
+    }
+}
+
+/// Get the pinyin of an item without tones alongside with the position and the tone of each syllable
+///
+/// # Arguments
+///
+/// * `item` - &Item
+fn item_syllables(item: &Item) -> (String, Vec<(Range<usize>, u32)>) {
+    let mut letters = String::new();
+    let mut syllables = Vec::new();
+
+    for syllable in &item.pinyin_tone_number {
+        let tones = PinyinNumber(syllable.as_str()).into_tones();
+        if tones.letters.is_empty() {
+            continue;
+        }
+
+        let start = letters.chars().count();
+        letters.extend(&tones.letters);
+
+        let tone = tones
+            .tones
+            .last()
+            .map(|(_, tone)| *tone)
+            .unwrap_or(NEUTRAL_TONE);
+
+        syllables.push((start..letters.chars().count(), tone));
+    }
+
+    (letters, syllables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cedict::Dictionary;
+    use crate::variant::KeyVariant;
+    use std::path::PathBuf;
+
+    #[test]
+    fn expect_to_search_pinyin_in_any_style() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        for query in ["ni3 hao3", "nǐ hǎo", "nihao", "ni hao", "ni3hao3"] {
+            let results = dictionary.search_pinyin(query);
+
+            assert_eq!(results.len(), 2, "{query}");
+            assert_eq!(results[0].item.traditional_character, "你好", "{query}");
+        }
+
+        let results = dictionary.search_pinyin("ni2hao1");
+        assert_eq!(results[0].item.traditional_character, "泥蒿");
+        assert_eq!(results[0].kind, PinyinMatchKind::Exact);
+        assert_eq!(results[1].kind, PinyinMatchKind::ToneMismatch);
+
+        // Syllables without tones are matched with any tone
+        let results = dictionary.search_pinyin("ni3 hao");
+        assert_eq!(results[0].item.traditional_character, "你好");
+        assert_eq!(results[0].kind, PinyinMatchKind::Toneless);
+        assert_eq!(results[1].kind, PinyinMatchKind::ToneMismatch);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::cedict::Item;
+use std::collections::HashMap;
+
+// Constant
+// Weight given to the words which do not have a frequency
+const DEFAULT_PER_MILLION: f64 = 1.0;
+
+/// CharacterReading is a reading of a character with its weight. The weight is the sum of the frequency of
+/// every words where the character is read this way e.g: 行 [hang2] -> 银行, 行业...
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterReading {
+    pub pinyin: String,
+    pub weight: f64,
+}
+
+/// PolyphoneIndex index the readings of each character from the words of the dictionary. The readings
+/// of a character are sorted from the most common to the least common one
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PolyphoneIndex {
+    characters: HashMap<char, Vec<CharacterReading>>,
+}
+
+impl PolyphoneIndex {
+    /// Build the polyphone index from the list of items. The words whose number of syllables does not match
+    /// their number of characters are skipped e.g: erhua entries
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - &[Item]
+    pub(crate) fn new(items: &[Item]) -> Self {
+        let mut characters: HashMap<char, Vec<CharacterReading>> = HashMap::new();
+
+        for item in items {
+            let weight = item
+                .frequency
+                .map_or(DEFAULT_PER_MILLION, |frequency| frequency.per_million);
+
+            let mut pairs = Vec::new();
+            for headword in [&item.simplified_character, &item.traditional_character] {
+                if headword.chars().count() != item.pinyin_tone_number.len() {
+                    continue;
+                }
+
+                pairs.extend(headword.chars().zip(&item.pinyin_tone_number));
+            }
+
+            // A character which is the same in both variants is only counted once
+            pairs.sort_unstable();
+            pairs.dedup();
+
+            for (character, pinyin) in pairs {
+                let pinyin = pinyin.to_lowercase();
+                let readings = characters.entry(character).or_default();
+                match readings.iter_mut().find(|reading| reading.pinyin == pinyin) {
+                    Some(reading) => reading.weight += weight,
+                    None => readings.push(CharacterReading { pinyin, weight }),
+                }
+            }
+        }
+
+        // The sort is stable hence readings with the same weight keep the order of the cedict file
+        for readings in characters.values_mut() {
+            readings.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        }
+
+        Self { characters }
+    }
+
+    /// Get the readings of a character. The most common reading come first
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - char
+    pub(crate) fn readings(&self, character: char) -> &[CharacterReading] {
+        self.characters
+            .get(&character)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the weight of a reading of a character. It's 0 when the character is never read this way
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - char
+    /// * `pinyin` - &str
+    pub(crate) fn weight(&self, character: char, pinyin: &str) -> f64 {
+        self.readings(character)
+            .iter()
+            .find(|reading| reading.pinyin.eq_ignore_ascii_case(pinyin))
+            .map_or(0.0, |reading| reading.weight)
+    }
+}
@@ -0,0 +1,208 @@
+use crate::cedict::Item;
+use crate::variant::KeyVariant;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Constant
+const ROOT: usize = 0;
+
+/// PrefixIndex is a trie of the headwords of the entries. It allows to find the headwords starting
+/// with a prefix and the longest headword which is a prefix of a text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PrefixIndex {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct Node {
+    children: BTreeMap<char, usize>,
+    items: Vec<usize>,
+}
+
+/// LongestMatch is the longest headword which is a prefix of a text with the entries of this headword
+#[derive(Debug, Clone, Serialize)]
+pub struct LongestMatch<'a, 't> {
+    pub headword: &'t str,
+    pub items: Vec<&'a Item>,
+}
+
+impl PrefixIndex {
+    /// Build the prefix index of the items for the given key variant
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - &[Item]
+    /// * `key_variant` - &KeyVariant
+    pub(crate) fn new(items: &[Item], key_variant: &KeyVariant) -> Self {
+        let mut index = Self {
+            nodes: vec![Node::default()],
+        };
+
+        for (idx, item) in items.iter().enumerate() {
+            let mut node = ROOT;
+            for c in item.get_character_for_key_variant(key_variant).chars() {
+                node = match index.nodes[node].children.get(&c) {
+                    Some(child) => *child,
+                    None => {
+                        index.nodes.push(Node::default());
+                        let child = index.nodes.len() - 1;
+                        index.nodes[node].children.insert(c, child);
+
+                        child
+                    }
+                };
+            }
+
+            index.nodes[node].items.push(idx);
+        }
+
+        index
+    }
+
+    /// Get every items whose headword start with the prefix. Shorter headwords come first.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `items` - &[Item]
+    /// * `prefix` - S
+    pub(crate) fn prefix_search<'a, S: AsRef<str>>(
+        &self,
+        items: &'a [Item],
+        prefix: S,
+    ) -> Vec<&'a Item> {
+        let Some(start) = prefix
+            .as_ref()
+            .chars()
+            .try_fold(ROOT, |node, c| self.nodes[node].children.get(&c).copied())
+        else {
+            return Vec::new();
+        };
+
+        // Walk the trie breadth first so that shorter headwords are collected first
+        let mut found = Vec::new();
+        let mut level = vec![start];
+        while !level.is_empty() {
+            let mut next_level = Vec::new();
+            for node in level {
+                found.extend(self.nodes[node].items.iter().map(|idx| &items[*idx]));
+                next_level.extend(self.nodes[node].children.values());
+            }
+
+            level = next_level;
+        }
+
+        found
+    }
+
+    /// Get the longest headword which is a prefix of the text
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `items` - &'a [Item]
+    /// * `text` - &'t str
+    pub(crate) fn longest_prefix_match<'a, 't>(
+        &self,
+        items: &'a [Item],
+        text: &'t str,
+    ) -> Option<LongestMatch<'a, 't>> {
+        let mut node = ROOT;
+        let mut longest = None;
+
+        for (offset, c) in text.char_indices() {
+            match self.nodes[node].children.get(&c) {
+                Some(child) => node = *child,
+                None => break,
+            }
+
+            if !self.nodes[node].items.is_empty() {
+                longest = Some((offset + c.len_utf8(), node));
+            }
+        }
+
+        longest.map(|(end, node)| LongestMatch {
+            headword: &text[..end],
+            items: self.nodes[node]
+                .items
+                .iter()
+                .map(|idx| &items[*idx])
+                .collect(),
+        })
+    }
+
+    /// Get every headwords which are a prefix of a text. Each match is the end of the headword in the text
+    /// with the indexes of its entries. Shorter headwords come first.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &str
+    pub(crate) fn prefix_matches(&self, text: &str) -> Vec<(usize, &[usize])> {
+        let mut node = ROOT;
+        let mut matches = Vec::new();
+
+        for (offset, c) in text.char_indices() {
+            match self.nodes[node].children.get(&c) {
+                Some(child) => node = *child,
+                None => break,
+            }
+
+            if !self.nodes[node].items.is_empty() {
+                matches.push((offset + c.len_utf8(), self.nodes[node].items.as_slice()));
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cedict::Dictionary;
+    use crate::variant::KeyVariant;
+    use std::path::PathBuf;
+
+    #[test]
+    fn expect_to_search_headwords_by_prefix() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let items = dictionary.prefix_search("中國", &KeyVariant::Traditional);
+        let headwords = items
+            .iter()
+            .map(|item| item.traditional_character.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(headwords, vec!["中國", "中國人"]);
+
+        let items = dictionary.prefix_search("中国", &KeyVariant::Simplified);
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn expect_to_find_the_longest_prefix_match() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        let found = dictionary
+            .longest_prefix_match("中国人很好", &KeyVariant::Simplified)
+            .unwrap();
+        assert_eq!(found.headword, "中国人");
+        assert_eq!(
+            found.items[0].pinyin_tone_number,
+            vec!["Zhong1", "guo2", "ren2"]
+        );
+
+        assert!(
+            dictionary
+                .longest_prefix_match("你们", &KeyVariant::Simplified)
+                .is_none()
+        );
+    }
+}
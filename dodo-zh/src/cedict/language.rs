@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// GlossLanguage is the language of the translations of a dictionary which use the cedict format
+/// e.g: CC-CEDICT (english), CFDICT (french) or HanDeDict (german)
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GlossLanguage {
+    #[default]
+    English,
+    French,
+    German,
+    /// Any other language identified by its code e.g: "es"
+    Other(String),
+}
+
+impl GlossLanguage {
+    /// Return the ISO 639-1 code of the language e.g: "fr"
+    pub fn code(&self) -> &str {
+        match self {
+            GlossLanguage::English => "en",
+            GlossLanguage::French => "fr",
+            GlossLanguage::German => "de",
+            GlossLanguage::Other(code) => code,
+        }
+    }
+}
+
+impl FromStr for GlossLanguage {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = match s.trim().to_lowercase().as_str() {
+            "en" | "english" => GlossLanguage::English,
+            "fr" | "french" => GlossLanguage::French,
+            "de" | "german" => GlossLanguage::German,
+            code => GlossLanguage::Other(code.to_string()),
+        };
+
+        Ok(language)
+    }
+}
+
+impl fmt::Display for GlossLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+// Constant
+pub(crate) const METADATA_PREFIX: &str = "#!";
+const METADATA_SEPARATOR: char = '=';
+const DEFAULT_LICENSE: &str = "https://creativecommons.org/licenses/by-sa/4.0/";
+const DEFAULT_PUBLISHER: &str = "MDBG";
+
+/// DictionaryMetadata contains the metadata which are defined in the header of the cedict file
+/// with the format `#! key=value`
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionaryMetadata {
+    pub version: Option<String>,
+    pub subversion: Option<String>,
+    pub format: Option<String>,
+    pub charset: Option<String>,
+    pub entries: Option<usize>,
+    pub publisher: Option<String>,
+    pub license: Option<String>,
+    pub date: Option<String>,
+}
+
+impl DictionaryMetadata {
+    /// Parse a metadata line e.g: `#! version=1` and set the related field.
+    /// Unknown keys are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `line` - S
+    pub(crate) fn parse_line<S: AsRef<str>>(&mut self, line: S) {
+        let Some((key, value)) = line
+            .as_ref()
+            .trim_start_matches(METADATA_PREFIX)
+            .split_once(METADATA_SEPARATOR)
+        else {
+            return;
+        };
+
+        let value = Some(value.trim().to_string());
+        match key.trim() {
+            "version" => self.version = value,
+            "subversion" => self.subversion = value,
+            "format" => self.format = value,
+            "charset" => self.charset = value,
+            "entries" => self.entries = value.and_then(|v| v.parse().ok()),
+            "publisher" => self.publisher = value,
+            "license" => self.license = value,
+            "date" => self.date = value,
+            _ => {}
+        }
+    }
+
+    /// Rewrite the `#! entries=N` line of the header with the given number of entries. The spacing and the line
+    /// ending of the line are kept. Other lines are not changed and `None` is returned
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - &str
+    /// * `entries` - usize
+    pub(crate) fn rewrite_entries_line(line: &str, entries: usize) -> Option<String> {
+        let (key, value) = line
+            .strip_prefix(METADATA_PREFIX)?
+            .split_once(METADATA_SEPARATOR)?;
+        if key.trim() != "entries" {
+            return None;
+        }
+
+        let content = line.trim_end_matches(['\r', '\n']);
+        let line_ending = &line[content.len()..];
+        let value_start = content.len() - value.trim_end_matches(['\r', '\n']).len();
+        let spaces = value.len() - value.trim_start().len();
+
+        Some(format!(
+            "{}{entries}{line_ending}",
+            &content[..value_start + spaces]
+        ))
+    }
+
+    /// Return the full version of the dictionary e.g: 1.0
+    pub fn full_version(&self) -> Option<String> {
+        match (&self.version, &self.subversion) {
+            (Some(version), Some(subversion)) => Some(format!("{version}.{subversion}")),
+            (Some(version), None) => Some(version.to_owned()),
+            _ => None,
+        }
+    }
+
+    /// Return the attribution required by the CC-BY-SA license of the cedict dictionary.
+    /// The MDBG publisher and the CC-BY-SA 4.0 license are used when the header does not define them.
+    pub fn attribution(&self) -> String {
+        let publisher = self.publisher.as_deref().unwrap_or(DEFAULT_PUBLISHER);
+        let license = self.license.as_deref().unwrap_or(DEFAULT_LICENSE);
+
+        let mut attribution = String::from("CC-CEDICT");
+        if let Some(version) = self.full_version() {
+            attribution.push_str(&format!(" {version}"));
+        }
+
+        if let Some(date) = &self.date {
+            attribution.push_str(&format!(" ({date})"));
+        }
+
+        format!("{attribution} published by {publisher} under the license {license}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_to_parse_metadata_lines() {
+        let mut metadata = DictionaryMetadata::default();
+        metadata.parse_line("#! version=1");
+        metadata.parse_line("#! subversion=0");
+        metadata.parse_line("#! entries=124006");
+        metadata.parse_line("#! date=2024-06-05T07:37:48Z");
+        metadata.parse_line("#! unknown=value");
+
+        assert_eq!(metadata.full_version().unwrap(), "1.0");
+        assert_eq!(metadata.entries, Some(124006));
+        assert_eq!(
+            DictionaryMetadata::rewrite_entries_line("#! entries= 124006\r\n", 3).unwrap(),
+            "#! entries= 3\r\n"
+        );
+        assert!(DictionaryMetadata::rewrite_entries_line("#! version=1\n", 3).is_none());
+        assert_eq!(
+            metadata.attribution(),
+            "CC-CEDICT 1.0 (2024-06-05T07:37:48Z) published by MDBG under the license https://creativecommons.org/licenses/by-sa/4.0/"
+        );
+    }
+}
@@ -0,0 +1,837 @@
+use crate::error::Error;
+use crate::variant::KeyVariant;
+use diagnostic::LineError;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs::File, io::Read, path::PathBuf, sync::OnceLock};
+use writer::LayoutLine;
+
+// Constant
+const CEDICT_SLASH: &str = "/";
+const CEDICT_BRACKET: [char; 2] = ['[', ']'];
+const JYUTPING_BRACE: [char; 2] = ['{', '}'];
+const VALID_LINE_FILTER: [char; 2] = ['#', '%'];
+
+mod compression;
+mod diagnostic;
+mod diff;
+mod frequency;
+mod index;
+mod language;
+mod metadata;
+mod multilingual;
+mod overlay;
+mod reader;
+mod reference;
+mod sense;
+mod snapshot;
+mod validation;
+mod writer;
+
+pub use compression::{Compression, DecompressReader};
+pub use diagnostic::{Diagnostic, DiagnosticKind};
+pub use diff::{DictionaryDiff, FieldChange, ModifiedEntry};
+pub use frequency::{
+    Frequency, FrequencyList, FrequencyRanked, filter_by_frequency, sort_by_frequency,
+};
+pub use index::{
+    CharacterReading, LongestMatch, PinyinMatch, PinyinMatchKind, TranslationMatch,
+    TranslationMatchKind,
+};
+pub(crate) use index::{EnglishIndex, PinyinIndex, PolyphoneIndex, PrefixIndex};
+pub use language::GlossLanguage;
+pub use metadata::DictionaryMetadata;
+pub use multilingual::{MultilingualDictionary, MultilingualItem};
+pub use overlay::{Overlay, OverlayPriority};
+pub use reader::CedictReader;
+pub use reference::{DanglingReference, LinkedEntry};
+pub use sense::{Reference, ReferenceKind, Sense, SenseKind, SenseLabel, SenseTag};
+pub use snapshot::SnapshotHeader;
+pub use validation::{Issue, Rule, Severity, ValidationReport, Validator};
+pub use writer::CedictWriter;
+
+/// Dictionary keep every entry of the cedict file. A headword may have several entries
+/// e.g: 了 [le5] and 了 [liao3]. Hence each headword point to the list of its entries.
+/// Each entry is stored once and is indexed by both its simplified and its traditional headword.
+/// The key variant is the script used by `get` and by the overlays.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    pub metadata: DictionaryMetadata,
+    items: Vec<Item>,
+    simplified_headwords: HashMap<String, Vec<usize>>,
+    traditional_headwords: HashMap<String, Vec<usize>>,
+    key_variant: KeyVariant,
+    language: GlossLanguage,
+    source_checksum: u32,
+    layout: Vec<LayoutLine>,
+    english_index: OnceLock<EnglishIndex>,
+    pinyin_index: OnceLock<PinyinIndex>,
+    simplified_prefix_index: OnceLock<PrefixIndex>,
+    traditional_prefix_index: OnceLock<PrefixIndex>,
+    polyphone_index: OnceLock<PolyphoneIndex>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub traditional_character: String,
+    pub simplified_character: String,
+    pub pinyin_tone_number: Vec<String>,
+    pub translations: Vec<String>,
+    /// Cantonese readings of the CC-Canto extension e.g: `{nei5 hou2}`
+    pub jyutping: Option<Vec<String>>,
+    /// Name of the overlay which the item come from. The item come from the base dictionary when it's `None`
+    pub layer: Option<String>,
+    /// Language of the translations
+    pub language: GlossLanguage,
+    /// Frequency of the simplified headword in a corpus. It's `None` until a frequency list is applied
+    pub frequency: Option<Frequency>,
+}
+
+impl Dictionary {
+    /// Create a new cedict dictionnary from the file path. The loading fail on the first malformed line
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - PathBuf
+    /// * `key_variant` - KeyVariant
+    pub fn new(path: &PathBuf, key_variant: KeyVariant) -> Result<Dictionary, Error> {
+        Dictionary::from_reader(File::open(path)?, key_variant)
+    }
+
+    /// Create a new cedict dictionnary from the file path by skipping the malformed lines. The skipped lines
+    /// are returned as a list of diagnostics alongside with the dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - PathBuf
+    /// * `key_variant` - KeyVariant
+    pub fn new_lenient(
+        path: &PathBuf,
+        key_variant: KeyVariant,
+    ) -> Result<(Dictionary, Vec<Diagnostic>), Error> {
+        Dictionary::from_reader_lenient(File::open(path)?, key_variant)
+    }
+
+    /// Create a new cedict dictionnary from any reader e.g: a file, a socket or a buffer.
+    /// The loading fail on the first malformed line
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    /// * `key_variant` - KeyVariant
+    pub fn from_reader<R: Read>(reader: R, key_variant: KeyVariant) -> Result<Dictionary, Error> {
+        let (dictionary, _) = Dictionary::load(reader, key_variant, false)?;
+
+        Ok(dictionary)
+    }
+
+    /// Create a new cedict dictionnary from any reader by skipping the malformed lines
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    /// * `key_variant` - KeyVariant
+    pub fn from_reader_lenient<R: Read>(
+        reader: R,
+        key_variant: KeyVariant,
+    ) -> Result<(Dictionary, Vec<Diagnostic>), Error> {
+        Dictionary::load(reader, key_variant, true)
+    }
+
+    /// Create a new cedict dictionnary from the content of a cedict file
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - &str
+    /// * `key_variant` - KeyVariant
+    pub fn from_str(content: &str, key_variant: KeyVariant) -> Result<Dictionary, Error> {
+        Dictionary::from_bytes(content.as_bytes(), key_variant)
+    }
+
+    /// Create a new cedict dictionnary from a buffer of bytes e.g: a file embedded with `include_bytes!`
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - &[u8]
+    /// * `key_variant` - KeyVariant
+    pub fn from_bytes(bytes: &[u8], key_variant: KeyVariant) -> Result<Dictionary, Error> {
+        Dictionary::from_reader(bytes, key_variant)
+    }
+
+    /// Load the cedict lines of the reader. Malformed lines are either skipped and reported as diagnostics
+    /// when the loading is lenient, or returned as an error
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    /// * `key_variant` - KeyVariant
+    /// * `lenient` - bool
+    fn load<R: Read>(
+        reader: R,
+        key_variant: KeyVariant,
+        lenient: bool,
+    ) -> Result<(Dictionary, Vec<Diagnostic>), Error> {
+        let mut reader = CedictReader::with_text_lines(reader, true)?;
+        let mut dictionary = Dictionary {
+            key_variant,
+            ..Default::default()
+        };
+
+        let mut diagnostics = Vec::new();
+        while let Some(entry) = reader.next_entry_line() {
+            let entry = entry?;
+            dictionary.push_text_lines(reader.take_text_lines());
+
+            match entry.item {
+                Ok(item) => {
+                    dictionary.layout.push(LayoutLine::entry(
+                        dictionary.items.len(),
+                        &item,
+                        entry.raw,
+                    ));
+                    dictionary.insert(item);
+                }
+                Err(err) if lenient => {
+                    // The malformed line is kept as it is so that the dictionary can be written back
+                    dictionary.layout.push(LayoutLine::Text(entry.raw));
+                    diagnostics.push(Diagnostic {
+                        kind: DiagnosticKind::Parse,
+                        line_number: entry.line_number,
+                        line: entry.line,
+                        column: Some(err.column),
+                        reason: err.reason,
+                    });
+                }
+                Err(err) => {
+                    return Err(Error::ParseLine {
+                        line: entry.line_number,
+                        column: err.column,
+                        reason: err.reason,
+                    });
+                }
+            }
+        }
+
+        dictionary.push_text_lines(reader.take_text_lines());
+
+        // Metadata lines may be found after the first entry. Hence it's only set once every lines has been read.
+        dictionary.metadata = reader.metadata().to_owned();
+        dictionary.source_checksum = reader.checksum();
+
+        // The header of cedict is often slightly out of date. Hence the mismatch is only reported
+        if let Some(reason) = dictionary.entries_count_mismatch()
+            && lenient
+        {
+            let (line_number, line) = reader.entries_line().cloned().unwrap_or_default();
+            diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::EntriesHeader,
+                line_number,
+                line,
+                column: None,
+                reason,
+            });
+        }
+
+        Ok((dictionary, diagnostics))
+    }
+
+    /// Get every entries of the given headword. An empty list is returned when the headword
+    /// does not exist in the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    pub fn get<S: AsRef<str>>(&self, headword: S) -> Vec<&Item> {
+        self.get_with_variant(headword, &self.key_variant)
+    }
+
+    /// Get every entries of the given headword written in the given variant
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    /// * `variant` - &KeyVariant
+    pub fn get_with_variant<S: AsRef<str>>(&self, headword: S, variant: &KeyVariant) -> Vec<&Item> {
+        self.headwords(variant)
+            .get(headword.as_ref())
+            .map(|indexes| indexes.iter().map(|idx| &self.items[*idx]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get every entries of the given headword whatever the script of the headword is. The entries
+    /// are returned in the order of the cedict file
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dodo_zh::cedict::Dictionary;
+    /// use dodo_zh::variant::KeyVariant;
+    ///
+    /// let dictionary = Dictionary::from_str("摯友 挚友 [zhi4 you3] /close friend/", KeyVariant::Simplified).unwrap();
+    /// assert_eq!(dictionary.lookup("摯友"), dictionary.lookup("挚友"));
+    /// ```
+    pub fn lookup<S: AsRef<str>>(&self, headword: S) -> Vec<&Item> {
+        let headword = headword.as_ref();
+        let mut indexes = [&self.simplified_headwords, &self.traditional_headwords]
+            .into_iter()
+            .filter_map(|headwords| headwords.get(headword))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        // An entry whose simplified and traditional headwords are the same is found in both indexes
+        indexes.sort_unstable();
+        indexes.dedup();
+
+        indexes.into_iter().map(|idx| &self.items[idx]).collect()
+    }
+
+    /// Check whether the headword exist in the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    pub fn contains<S: AsRef<str>>(&self, headword: S) -> bool {
+        self.contains_with_variant(headword, &self.key_variant)
+    }
+
+    /// Check whether the headword written in the given variant exist in the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    /// * `variant` - &KeyVariant
+    pub fn contains_with_variant<S: AsRef<str>>(&self, headword: S, variant: &KeyVariant) -> bool {
+        self.headwords(variant).contains_key(headword.as_ref())
+    }
+
+    /// Get every entries of the dictionary in the order of the cedict file
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Consume the dictionary and return every entries in the order of the cedict file
+    pub fn into_items(self) -> Vec<Item> {
+        self.items
+    }
+
+    /// Return the number of entries
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether the dictionary has no entries
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Return the variant used to key the headwords
+    pub fn key_variant(&self) -> &KeyVariant {
+        &self.key_variant
+    }
+
+    /// Set the language of the translations of the dictionary and of its entries. The language is
+    /// english by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `language` - GlossLanguage
+    pub fn with_language(mut self, language: GlossLanguage) -> Self {
+        for item in &mut self.items {
+            item.language = language.clone();
+        }
+
+        self.language = language;
+        self
+    }
+
+    /// Return the language of the translations
+    pub fn language(&self) -> &GlossLanguage {
+        &self.language
+    }
+
+    /// Return the CRC32 checksum of the cedict file used to create the dictionary
+    pub fn source_checksum(&self) -> u32 {
+        self.source_checksum
+    }
+
+    /// Add an item at the end of the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `item` - Item
+    pub fn push(&mut self, item: Item) {
+        self.layout.push(LayoutLine::Entry {
+            item: self.items.len(),
+            raw: None,
+        });
+        self.insert(item);
+        self.reset_indexes();
+    }
+
+    /// Only keep the items for which the predicate return true. The order of the remaining items is preserved
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `predicate` - F
+    pub fn retain<F: FnMut(&Item) -> bool>(&mut self, mut predicate: F) {
+        self.retain_indexes(|_, item| predicate(item));
+    }
+
+    /// Only keep the items for which the predicate return true. The predicate receive the index of the item
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `predicate` - F
+    fn retain_indexes<F: FnMut(usize, &Item) -> bool>(&mut self, mut predicate: F) {
+        let mut new_indexes = Vec::with_capacity(self.items.len());
+        let mut items = Vec::with_capacity(self.items.len());
+        for (idx, item) in std::mem::take(&mut self.items).into_iter().enumerate() {
+            match predicate(idx, &item) {
+                true => {
+                    new_indexes.push(Some(items.len()));
+                    items.push(item);
+                }
+                false => new_indexes.push(None),
+            }
+        }
+
+        self.layout.retain_mut(|line| match line {
+            LayoutLine::Text(_) => true,
+            LayoutLine::Entry { item, .. } => match new_indexes[*item] {
+                Some(idx) => {
+                    *item = idx;
+                    true
+                }
+                None => false,
+            },
+        });
+
+        self.simplified_headwords.clear();
+        self.traditional_headwords.clear();
+        for item in items {
+            self.insert(item);
+        }
+
+        self.reset_indexes();
+    }
+
+    /// Add the comments and the metadata lines to the layout of the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `lines` - Vec<String>
+    fn push_text_lines(&mut self, lines: Vec<String>) {
+        self.layout.extend(lines.into_iter().map(LayoutLine::Text));
+    }
+
+    /// Drop the indexes so that they're built again with the current items on the next search
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    fn reset_indexes(&mut self) {
+        self.english_index = OnceLock::new();
+        self.pinyin_index = OnceLock::new();
+        self.simplified_prefix_index = OnceLock::new();
+        self.traditional_prefix_index = OnceLock::new();
+        self.polyphone_index = OnceLock::new();
+    }
+
+    /// Check whether the number of entries does not match the number of entries declared in the header
+    /// e.g: `#! entries=124006`. The loading does not fail on a mismatch. The lenient loading report it as a diagnostic
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub fn entries_count_mismatch(&self) -> Option<String> {
+        match self.metadata.entries {
+            Some(entries) if entries != self.items.len() => Some(format!(
+                "the header declare {entries} entries but {} entries were parsed",
+                self.items.len()
+            )),
+            _ => None,
+        }
+    }
+
+    /// Insert an item into the dictionary. Items which share the same headword are kept
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `item` - Item
+    fn insert(&mut self, item: Item) {
+        let idx = self.items.len();
+
+        self.simplified_headwords
+            .entry(item.simplified_character.clone())
+            .or_default()
+            .push(idx);
+        self.traditional_headwords
+            .entry(item.traditional_character.clone())
+            .or_default()
+            .push(idx);
+        self.items.push(item);
+    }
+
+    /// Get the index of the headwords written in the given variant
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `variant` - &KeyVariant
+    fn headwords(&self, variant: &KeyVariant) -> &HashMap<String, Vec<usize>> {
+        match variant {
+            KeyVariant::Simplified => &self.simplified_headwords,
+            KeyVariant::Traditional => &self.traditional_headwords,
+        }
+    }
+}
+
+impl TryFrom<String> for Item {
+    type Error = Error;
+
+    fn try_from(line: String) -> Result<Self, Self::Error> {
+        Item::parse_line(&line)
+            .map_err(|err| Error::Parse(format!("{} at column {}", err.reason, err.column)))
+    }
+}
+
+impl Item {
+    /// Parse a cedict line with the format `<traditional> <simplified> [<pinyin>] {<jyutping>} /<translations>/`.
+    /// The jyutping is optional. The translations may be missing when the line has a jyutping
+    /// e.g: the lines of the CC-CEDICT cantonese readings file
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - &str
+    pub(crate) fn parse_line(line: &str) -> Result<Self, LineError> {
+        let (rest, translations) = match line.split_once(CEDICT_SLASH) {
+            Some(parts) => parts,
+            None if line.contains(JYUTPING_BRACE[0]) => (line, ""),
+            None => {
+                return Err(LineError::new(
+                    line,
+                    line.len(),
+                    "Unable to found the translations",
+                ));
+            }
+        };
+
+        let translations = translations
+            .split(CEDICT_SLASH)
+            .filter_map(filter_empty_check)
+            .collect::<Vec<_>>();
+
+        let open = rest
+            .find(CEDICT_BRACKET[0])
+            .ok_or_else(|| LineError::new(line, rest.len(), "Unable to found pinyin"))?;
+
+        let close = rest[open..]
+            .find(CEDICT_BRACKET[1])
+            .map(|idx| open + idx)
+            .ok_or_else(|| {
+                LineError::new(
+                    line,
+                    open,
+                    "Unable to found the closing bracket of the pinyin",
+                )
+            })?;
+
+        let jyutping = Item::parse_jyutping(line, rest, close + 1)?;
+
+        let pinyin = rest[open + 1..close]
+            .split_whitespace()
+            .filter_map(filter_empty_check)
+            .collect::<Vec<String>>();
+
+        // Splitting the whitespace allow of the rest allow us to get the traditional & simplified chinese character
+        let [traditional_character, simplified_character] =
+            rest[..open].split_whitespace().collect::<Vec<_>>()[..]
+        else {
+            return Err(LineError::new(
+                line,
+                0,
+                "Unable to found the traditional and simplified characters",
+            ));
+        };
+
+        Ok(Item {
+            traditional_character: traditional_character.to_string(),
+            simplified_character: simplified_character.to_string(),
+            pinyin_tone_number: pinyin,
+            translations,
+            jyutping,
+            layer: None,
+            language: GlossLanguage::default(),
+            frequency: None,
+        })
+    }
+
+    /// Parse the optional jyutping block which is between the pinyin and the translations
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - &str
+    /// * `rest` - &str
+    /// * `start` - usize
+    fn parse_jyutping(
+        line: &str,
+        rest: &str,
+        start: usize,
+    ) -> Result<Option<Vec<String>>, LineError> {
+        let block = rest[start..].trim();
+        if block.is_empty() {
+            return Ok(None);
+        }
+
+        let offset = start
+            + rest[start..]
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or_default();
+        if !block.starts_with(JYUTPING_BRACE[0]) {
+            return Err(LineError::new(
+                line,
+                offset,
+                "Unexpected content between the pinyin and the translations",
+            ));
+        }
+
+        let Some((jyutping, remaining)) = block[1..].split_once(JYUTPING_BRACE[1]) else {
+            return Err(LineError::new(
+                line,
+                offset,
+                "Unable to found the closing brace of the jyutping",
+            ));
+        };
+
+        if !remaining.trim().is_empty() {
+            return Err(LineError::new(
+                line,
+                offset,
+                "Unexpected content between the jyutping and the translations",
+            ));
+        }
+
+        Ok(Some(
+            jyutping.split_whitespace().map(str::to_string).collect(),
+        ))
+    }
+
+    /// Parse the translations of the item into a list of structured senses. The raw translations
+    /// are still available in the `translations` field.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub fn senses(&self) -> Vec<Sense> {
+        self.translations.iter().map(Sense::parse).collect()
+    }
+
+    /// Get the classifiers (measure words) of the item
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub fn classifiers(&self) -> Vec<Reference> {
+        self.senses()
+            .into_iter()
+            .flat_map(|sense| sense.classifiers)
+            .collect()
+    }
+
+    /// Get the character for the given key variant
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Item
+    /// * `variant` - KeyVariant
+    pub(crate) fn get_character_for_key_variant(&self, variant: &KeyVariant) -> String {
+        match variant {
+            KeyVariant::Simplified => self.simplified_character.clone(),
+            KeyVariant::Traditional => self.traditional_character.clone(),
+        }
+    }
+}
+
+/// Filter empty string out and return a string value
+///
+/// # Arguments
+///
+/// * `s` - S
+fn filter_empty_check<S>(s: S) -> Option<String>
+where
+    S: AsRef<str>,
+{
+    if s.as_ref().is_empty() {
+        return None;
+    }
+
+    Some(s.as_ref().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_to_parse_line_item() {
+        let line = r"一動不動 一动不动 [yi1 dong4 bu4 dong4] /motionless/";
+        let item = Item::try_from(line.to_string());
+
+        assert!(item.is_ok());
+
+        let item = item.unwrap();
+        assert_eq!(item.traditional_character, "一動不動");
+        assert_eq!(item.simplified_character, "一动不动");
+        assert_eq!(
+            item.pinyin_tone_number,
+            vec!["yi1", "dong4", "bu4", "dong4"]
+        );
+        assert_eq!(item.translations, vec!["motionless"]);
+    }
+
+    #[test]
+    fn expect_to_parse_jyutping() {
+        let item =
+            Item::try_from("你好 你好 [ni3 hao3] {nei5 hou2} /hello; hi/".to_string()).unwrap();
+        assert_eq!(
+            item.jyutping,
+            Some(vec!["nei5".to_string(), "hou2".to_string()])
+        );
+        assert_eq!(item.translations, vec!["hello; hi"]);
+
+        let reading = Item::try_from("你好 你好 [ni3 hao3] {nei5 hou2}".to_string()).unwrap();
+        assert!(reading.translations.is_empty());
+        assert_eq!(reading.to_string(), "你好 你好 [ni3 hao3] {nei5 hou2}");
+
+        assert!(Item::try_from("你好 你好 [ni3 hao3] {nei5 hou2 /hello/".to_string()).is_err());
+    }
+
+    #[test]
+    fn expect_to_get_item_classifiers() {
+        let line = r"朋友 朋友 [peng2 you5] /friend/CL:個|个[ge4],位[wei4]/";
+        let item = Item::try_from(line.to_string()).unwrap();
+
+        let classifiers = item.classifiers();
+        assert_eq!(classifiers.len(), 2);
+        assert_eq!(classifiers[0].traditional_character, "個");
+        assert_eq!(item.senses()[0].kind, SenseKind::Definition);
+    }
+
+    #[test]
+    fn expect_to_keep_every_entries_of_an_headword() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        let items = dictionary.get("了");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].pinyin_tone_number, vec!["le5"]);
+        assert_eq!(items[1].pinyin_tone_number, vec!["liao3"]);
+
+        assert!(dictionary.get("你").is_empty());
+    }
+
+    #[test]
+    fn expect_to_lookup_headword_in_both_variants() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        assert_eq!(dictionary.lookup("摯友"), dictionary.lookup("挚友"));
+        assert_eq!(dictionary.lookup("摯友").len(), 1);
+        assert_eq!(dictionary.lookup("了").len(), 2);
+        assert!(dictionary.get("摯友").is_empty());
+        assert_eq!(
+            dictionary
+                .get_with_variant("摯友", &KeyVariant::Traditional)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn expect_to_report_line_error_column() {
+        let err =
+            Item::parse_line("一動不動 一动不动 [yi1 dong4 bu4 dong4 /motionless/").unwrap_err();
+
+        assert_eq!(err.column, 11);
+
+        let err = Item::parse_line("一動不動 [yi1 dong4 bu4 dong4] /motionless/").unwrap_err();
+        assert_eq!(err.column, 1);
+    }
+
+    #[test]
+    fn expect_to_load_leniently_with_diagnostics() {
+        let (dictionary, diagnostics) = Dictionary::new_lenient(
+            &PathBuf::from("../static/cedict_malformed_ts.u8"),
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].line_number, 3);
+        assert_eq!(diagnostics[0].column, Some(10));
+        assert_eq!(diagnostics[2].column, None);
+
+        let err = Dictionary::new(
+            &PathBuf::from("../static/cedict_malformed_ts.u8"),
+            KeyVariant::Simplified,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("line 3, column 10"));
+    }
+
+    #[test]
+    fn expect_to_load_from_str_and_bytes() {
+        let content = "#! entries=2\n她 她 [ta1] /she/\n我 我 [wo3] /I; me; my/\n";
+
+        let dictionary = Dictionary::from_str(content, KeyVariant::Simplified).unwrap();
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.get("我")[0].pinyin_tone_number, vec!["wo3"]);
+
+        let dictionary =
+            Dictionary::from_bytes(content.as_bytes(), KeyVariant::Traditional).unwrap();
+        assert!(dictionary.contains("她"));
+
+        // A stale header does not prevent the dictionary from being loaded
+        let dictionary =
+            Dictionary::from_str("#! entries=3\n她 她 [ta1] /she/\n", KeyVariant::Simplified)
+                .unwrap();
+        assert_eq!(
+            dictionary.entries_count_mismatch().unwrap(),
+            "the header declare 3 entries but 1 entries were parsed"
+        );
+    }
+
+    #[test]
+    fn expect_to_parse_header_metadata() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        assert_eq!(dictionary.metadata.format.as_deref(), Some("ts"));
+        assert_eq!(dictionary.metadata.entries, Some(dictionary.len()));
+    }
+}
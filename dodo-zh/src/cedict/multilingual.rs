@@ -0,0 +1,201 @@
+use super::{Dictionary, GlossLanguage, Item};
+use crate::variant::KeyVariant;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// MultilingualItem is an entry which contains the translations of every merged dictionaries
+/// grouped by their language
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MultilingualItem {
+    pub traditional_character: String,
+    pub simplified_character: String,
+    pub pinyin_tone_number: Vec<String>,
+    pub translations: BTreeMap<GlossLanguage, Vec<String>>,
+}
+
+/// MultilingualDictionary merge several dictionaries which use the cedict format in different languages
+/// e.g: CC-CEDICT, CFDICT and HanDeDict. Entries are keyed by their headword and their pinyin.
+#[derive(Debug, Default)]
+pub struct MultilingualDictionary {
+    items: Vec<MultilingualItem>,
+    entries: HashMap<(String, String), usize>,
+    headwords: HashMap<String, Vec<usize>>,
+    key_variant: KeyVariant,
+    languages: Vec<GlossLanguage>,
+}
+
+impl MultilingualDictionary {
+    /// Create an empty multilingual dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `key_variant` - KeyVariant
+    pub fn new(key_variant: KeyVariant) -> Self {
+        Self {
+            key_variant,
+            ..Default::default()
+        }
+    }
+
+    /// Create a multilingual dictionary by merging the given dictionaries in order
+    ///
+    /// # Arguments
+    ///
+    /// * `key_variant` - KeyVariant
+    /// * `dictionaries` - &[Dictionary]
+    pub fn from_dictionaries(key_variant: KeyVariant, dictionaries: &[Dictionary]) -> Self {
+        let mut multilingual = MultilingualDictionary::new(key_variant);
+        for dictionary in dictionaries {
+            multilingual.merge(dictionary);
+        }
+
+        multilingual
+    }
+
+    /// Merge the entries of a dictionary. The translations are added under the language of the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `dictionary` - &Dictionary
+    pub fn merge(&mut self, dictionary: &Dictionary) {
+        if !self.languages.contains(dictionary.language()) {
+            self.languages.push(dictionary.language().to_owned());
+        }
+
+        for item in dictionary.items() {
+            let headword = item.get_character_for_key_variant(&self.key_variant);
+            let key = (headword.clone(), pinyin_key(&item.pinyin_tone_number));
+
+            let idx = *self.entries.entry(key).or_insert_with(|| {
+                self.headwords
+                    .entry(headword)
+                    .or_default()
+                    .push(self.items.len());
+                self.items.push(MultilingualItem {
+                    traditional_character: item.traditional_character.clone(),
+                    simplified_character: item.simplified_character.clone(),
+                    pinyin_tone_number: item.pinyin_tone_number.clone(),
+                    ..Default::default()
+                });
+
+                self.items.len() - 1
+            });
+
+            let translations = self.items[idx]
+                .translations
+                .entry(item.language.clone())
+                .or_default();
+
+            for translation in &item.translations {
+                if !translations.contains(translation) {
+                    translations.push(translation.to_owned());
+                }
+            }
+        }
+    }
+
+    /// Get every entries of the given headword
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    pub fn get<S: AsRef<str>>(&self, headword: S) -> Vec<&MultilingualItem> {
+        self.headwords
+            .get(headword.as_ref())
+            .map(|indexes| indexes.iter().map(|idx| &self.items[*idx]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Find the entry which has the given headword and pinyin. The pinyin is compared regardless of its case
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    /// * `pinyin` - &[String]
+    pub fn find<S: AsRef<str>>(&self, headword: S, pinyin: &[String]) -> Option<&MultilingualItem> {
+        self.entries
+            .get(&(headword.as_ref().to_string(), pinyin_key(pinyin)))
+            .map(|idx| &self.items[*idx])
+    }
+
+    /// Find the entry which match the headword and the pinyin of an item
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `item` - &Item
+    pub fn find_item(&self, item: &Item) -> Option<&MultilingualItem> {
+        self.find(
+            item.get_character_for_key_variant(&self.key_variant),
+            &item.pinyin_tone_number,
+        )
+    }
+
+    /// Get every entries in the order of the merge
+    pub fn items(&self) -> &[MultilingualItem] {
+        &self.items
+    }
+
+    /// Get the languages of the merged dictionaries in the order of the merge
+    pub fn languages(&self) -> &[GlossLanguage] {
+        &self.languages
+    }
+
+    /// Return the number of entries
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether the dictionary has no entries
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Build the key of a pinyin. The case is ignored as dictionaries do not always capitalize proper nouns
+///
+/// # Arguments
+///
+/// * `pinyin` - &[String]
+fn pinyin_key(pinyin: &[String]) -> String {
+    pinyin.join(" ").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_to_merge_languages() {
+        let english = Dictionary::from_str(
+            "朋友 朋友 [peng2 you5] /friend/\n中國 中国 [Zhong1 guo2] /China/",
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+        let french = Dictionary::from_str(
+            "朋友 朋友 [peng2 you5] /ami/camarade/\n中國 中国 [zhong1 guo2] /Chine/\n你 你 [ni3] /tu/",
+            KeyVariant::Simplified,
+        )
+        .unwrap()
+        .with_language(GlossLanguage::French);
+
+        let multilingual =
+            MultilingualDictionary::from_dictionaries(KeyVariant::Simplified, &[english, french]);
+
+        assert_eq!(multilingual.len(), 3);
+        assert_eq!(
+            multilingual.languages(),
+            &[GlossLanguage::English, GlossLanguage::French]
+        );
+
+        let china = multilingual.get("中国")[0];
+        assert_eq!(china.translations[&GlossLanguage::English], vec!["China"]);
+        assert_eq!(china.translations[&GlossLanguage::French], vec!["Chine"]);
+
+        let ni = multilingual.find("你", &["ni3".to_string()]).unwrap();
+        assert!(!ni.translations.contains_key(&GlossLanguage::English));
+    }
+}
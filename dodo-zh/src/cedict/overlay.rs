@@ -0,0 +1,317 @@
+use super::writer::LayoutLine;
+use super::{CedictReader, Dictionary, Item};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+/// OverlayPriority decide what an entry of an overlay does to the entries of the dictionary which
+/// have the same characters and the same pinyin
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverlayPriority {
+    /// The entry is added alongside the existing entries
+    Add,
+    /// The entry replace the existing entries. The entry is added when there is no existing entries
+    #[default]
+    Replace,
+    /// The existing entries are deleted. The translations of the entry are ignored
+    Delete,
+    /// The cantonese readings of the entry are set on the existing entries e.g: the CC-CEDICT cantonese
+    /// readings file. Entries which do not exist are ignored
+    Readings,
+}
+
+/// Overlay is a layer of entries in the cedict format which is stacked on top of a dictionary
+/// e.g: company specific terms or corrected translations
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlay {
+    pub name: String,
+    pub priority: OverlayPriority,
+    items: Vec<Item>,
+}
+
+impl Overlay {
+    /// Create a new overlay from the path of a cedict file
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - S
+    /// * `path` - &PathBuf
+    /// * `priority` - OverlayPriority
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        path: &PathBuf,
+        priority: OverlayPriority,
+    ) -> Result<Overlay, Error> {
+        Overlay::from_reader(name, File::open(path)?, priority)
+    }
+
+    /// Create a new overlay from any reader. The loading fail on the first malformed line. An entry of a replace
+    /// overlay must have a pinyin since an entry without pinyin would replace every readings of its headword
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - S
+    /// * `reader` - R
+    /// * `priority` - OverlayPriority
+    pub fn from_reader<S: AsRef<str>, R: Read>(
+        name: S,
+        reader: R,
+        priority: OverlayPriority,
+    ) -> Result<Overlay, Error> {
+        let mut reader = CedictReader::new(reader)?;
+        let mut items = Vec::new();
+
+        while let Some(entry) = reader.next_entry_line() {
+            let entry = entry?;
+            let item = entry.item.map_err(|err| Error::ParseLine {
+                line: entry.line_number,
+                column: err.column,
+                reason: err.reason,
+            })?;
+
+            if priority == OverlayPriority::Replace && item.pinyin_tone_number.is_empty() {
+                return Err(Error::ParseLine {
+                    line: entry.line_number,
+                    column: entry.line.find('[').map_or(1, |offset| offset + 1),
+                    reason: "the entry of a replace overlay must have a pinyin".to_string(),
+                });
+            }
+
+            items.push(item);
+        }
+
+        Ok(Overlay {
+            name: name.as_ref().to_string(),
+            priority,
+            items,
+        })
+    }
+
+    /// Get the entries of the overlay
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+}
+
+impl Dictionary {
+    /// Stack an overlay on top of the dictionary. Overlays are applied in the order of the calls, hence the
+    /// last overlay win. The items which come from the overlay record the name of the overlay in their `layer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `overlay` - &Overlay
+    pub fn apply_overlay(&mut self, overlay: &Overlay) {
+        let mut deleted = HashSet::new();
+        let mut replaced = HashSet::new();
+
+        for overlay_item in &overlay.items {
+            let mut item = overlay_item.clone();
+            item.layer = Some(overlay.name.clone());
+            item.language = self.language.clone();
+
+            let matches = self
+                .matching_entries(&item)
+                .into_iter()
+                .filter(|idx| !deleted.contains(idx))
+                .collect::<Vec<_>>();
+
+            match (overlay.priority, matches.split_first()) {
+                (OverlayPriority::Add, _) => {
+                    let exists = matches.iter().any(|idx| {
+                        self.items[*idx].translations == item.translations
+                            && self.items[*idx].layer == item.layer
+                    });
+
+                    if !exists {
+                        self.push(item);
+                    }
+                }
+                (OverlayPriority::Replace, Some((first, rest))) => {
+                    self.items[*first] = item;
+                    replaced.insert(*first);
+                    deleted.extend(rest);
+                }
+                (OverlayPriority::Replace, None) => self.push(item),
+                (OverlayPriority::Delete, _) => deleted.extend(matches),
+                (OverlayPriority::Readings, _) => {
+                    for idx in matches {
+                        self.items[idx].jyutping = item.jyutping.clone();
+                        replaced.insert(idx);
+                    }
+                }
+            }
+        }
+
+        // The replaced entries are written with the new content instead of the raw line of the cedict file
+        for line in &mut self.layout {
+            if let LayoutLine::Entry { item, raw } = line
+                && replaced.contains(item)
+            {
+                *raw = None;
+            }
+        }
+
+        self.retain_indexes(|idx, _| !deleted.contains(&idx));
+    }
+
+    /// Stack several overlays on top of the dictionary in the given order
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `overlays` - &[Overlay]
+    pub fn apply_overlays(&mut self, overlays: &[Overlay]) {
+        for overlay in overlays {
+            self.apply_overlay(overlay);
+        }
+    }
+
+    /// Get the indexes of the entries which have the same characters as the item. The pinyin has to match as well
+    /// unless the item does not define one.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `item` - &Item
+    fn matching_entries(&self, item: &Item) -> Vec<usize> {
+        let pinyin = item.pinyin_tone_number.join(" ").to_lowercase();
+
+        self.headwords(&self.key_variant)
+            .get(&item.get_character_for_key_variant(&self.key_variant))
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|idx| {
+                let entry = &self.items[*idx];
+
+                entry.traditional_character == item.traditional_character
+                    && entry.simplified_character == item.simplified_character
+                    && (pinyin.is_empty()
+                        || entry.pinyin_tone_number.join(" ").to_lowercase() == pinyin)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+
+    fn load_dictionary() -> Dictionary {
+        Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn expect_to_stack_overlays() {
+        let mut dictionary = load_dictionary();
+        let len = dictionary.len();
+
+        let terms = Overlay::from_reader(
+            "terms",
+            "行 行 [hang2] /row; line; company/\n數據湖 数据湖 [shu4 ju4 hu2] /data lake/"
+                .as_bytes(),
+            OverlayPriority::Replace,
+        )
+        .unwrap();
+        let deleted = Overlay::from_reader(
+            "deleted",
+            "泥蒿 泥蒿 [] //".as_bytes(),
+            OverlayPriority::Delete,
+        )
+        .unwrap();
+
+        dictionary.apply_overlays(&[terms, deleted]);
+
+        assert_eq!(dictionary.len(), len);
+        assert!(!dictionary.contains("泥蒿"));
+
+        let hang = dictionary.get("行");
+        assert_eq!(hang.len(), 2);
+        assert_eq!(hang[0].translations, vec!["row; line; company"]);
+        assert_eq!(hang[0].layer.as_deref(), Some("terms"));
+        assert_eq!(hang[1].layer, None);
+
+        let lake = dictionary.get("數據湖");
+        assert_eq!(lake[0].layer.as_deref(), Some("terms"));
+        assert_eq!(dictionary.search_translation("data lake").len(), 1);
+    }
+
+    #[test]
+    fn expect_to_reject_replace_entry_without_pinyin() {
+        let overlay = Overlay::from_reader(
+            "terms",
+            "# comment\n行 行 [] /row/".as_bytes(),
+            OverlayPriority::Replace,
+        );
+        assert!(matches!(overlay, Err(Error::ParseLine { line: 2, .. })));
+
+        // An entry with a pinyin only replace the reading which has the same pinyin
+        let mut dictionary = load_dictionary();
+        let overlay = Overlay::from_reader(
+            "terms",
+            "行 行 [xing2] /to walk/".as_bytes(),
+            OverlayPriority::Replace,
+        )
+        .unwrap();
+        dictionary.apply_overlay(&overlay);
+
+        let pinyin = dictionary
+            .get("行")
+            .iter()
+            .map(|item| item.pinyin_tone_number.join(" "))
+            .collect::<Vec<_>>();
+        assert_eq!(pinyin, vec!["hang2", "xing2"]);
+        assert_eq!(dictionary.get("行")[1].translations, vec!["to walk"]);
+    }
+
+    #[test]
+    fn expect_to_add_overlay_entries() {
+        let mut dictionary = load_dictionary();
+        let overlay = Overlay::from_reader(
+            "extra",
+            "好 好 [hao3] /okay (company slang)/".as_bytes(),
+            OverlayPriority::Add,
+        )
+        .unwrap();
+
+        dictionary.apply_overlay(&overlay);
+        dictionary.apply_overlay(&overlay);
+
+        let hao = dictionary.get("好");
+        assert_eq!(hao.len(), 2);
+        assert_eq!(hao[1].layer.as_deref(), Some("extra"));
+    }
+
+    #[test]
+    fn expect_to_apply_cantonese_readings() {
+        let mut dictionary = load_dictionary();
+        let readings = Overlay::from_reader(
+            "canto",
+            "# CC-CEDICT cantonese readings\n你好 你好 [ni3 hao3] {nei5 hou2}\n粵語 粤语 [Yue4 yu3] {jyut6 jyu5}"
+                .as_bytes(),
+            OverlayPriority::Readings,
+        )
+        .unwrap();
+
+        let len = dictionary.len();
+        dictionary.apply_overlay(&readings);
+
+        assert_eq!(dictionary.len(), len);
+        let item = dictionary.get("你好")[0];
+        assert_eq!(
+            item.jyutping,
+            Some(vec!["nei5".to_string(), "hou2".to_string()])
+        );
+        assert_eq!(item.translations, vec!["hello; hi"]);
+        assert_eq!(item.layer, None);
+    }
+}
@@ -0,0 +1,242 @@
+use super::compression::DecompressReader;
+use super::diagnostic::LineError;
+use super::metadata::METADATA_PREFIX;
+use super::{DictionaryMetadata, Item, VALID_LINE_FILTER};
+use crate::error::Error;
+use crc32fast::Hasher;
+use std::io::{BufRead, BufReader, Read};
+
+// Constant
+const ENTRIES_METADATA: &str = "#! entries=";
+
+/// CedictReader read the entries of a cedict file one by one without keeping them in memory.
+/// The metadata of the header are read when the reader is created. Compressed files (zip, gzip, xz)
+/// are decompressed while being read.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::CedictReader;
+///
+/// let content = "#! version=1\n她 她 [ta1] /she/\n";
+/// let reader = CedictReader::new(content.as_bytes()).unwrap();
+/// assert_eq!(reader.metadata().version.as_deref(), Some("1"));
+///
+/// let count = reader.filter_map(Result::ok).count();
+/// assert_eq!(count, 1);
+/// ```
+#[derive(Debug)]
+pub struct CedictReader<R: Read> {
+    reader: BufReader<DecompressReader<R>>,
+    line_number: usize,
+    metadata: DictionaryMetadata,
+    entries_line: Option<(usize, String)>,
+    pending: Option<(usize, String)>,
+    hasher: Hasher,
+    keep_text_lines: bool,
+    text_lines: Vec<String>,
+}
+
+/// EntryLine is a cedict line which contains an entry alongside with the result of its parsing.
+/// The raw line keep the line ending as it is in the cedict file.
+#[derive(Debug)]
+pub(crate) struct EntryLine {
+    pub line_number: usize,
+    pub line: String,
+    pub raw: String,
+    pub item: Result<Item, LineError>,
+}
+
+impl<R: Read> CedictReader<R> {
+    /// Create a new cedict reader and read the header of the cedict file
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    pub fn new(reader: R) -> Result<Self, Error> {
+        CedictReader::with_text_lines(reader, false)
+    }
+
+    /// Create a new cedict reader which keep the comments and the metadata lines in order to
+    /// be able to write them back
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    /// * `keep_text_lines` - bool
+    pub(crate) fn with_text_lines(reader: R, keep_text_lines: bool) -> Result<Self, Error> {
+        let mut cedict_reader = CedictReader {
+            reader: BufReader::new(DecompressReader::new(reader)?),
+            line_number: 0,
+            metadata: DictionaryMetadata::default(),
+            entries_line: None,
+            pending: None,
+            hasher: Hasher::new(),
+            keep_text_lines,
+            text_lines: Vec::new(),
+        };
+
+        // Read the lines until the first entry so that the metadata are available before reading the entries.
+        cedict_reader.pending = cedict_reader.read_entry_line()?;
+
+        Ok(cedict_reader)
+    }
+
+    /// Get the metadata of the cedict file
+    pub fn metadata(&self) -> &DictionaryMetadata {
+        &self.metadata
+    }
+
+    /// Get the CRC32 checksum of the lines which have been read so far. Once every entries have been read
+    /// the checksum identify the content of the cedict file regardless of its line endings.
+    pub fn checksum(&self) -> u32 {
+        self.hasher.clone().finalize()
+    }
+
+    /// Read the remaining lines without parsing the entries and return the checksum of the cedict file
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    pub fn into_checksum(mut self) -> Result<u32, Error> {
+        while self.read_entry_line()?.is_some() {}
+
+        Ok(self.checksum())
+    }
+
+    /// Get the line number and the line of the `#! entries=` metadata if it has been read
+    pub(crate) fn entries_line(&self) -> Option<&(usize, String)> {
+        self.entries_line.as_ref()
+    }
+
+    /// Take the comments and the metadata lines which have been read since the last call. Lines are only
+    /// kept when the reader has been created with `with_text_lines`
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    pub(crate) fn take_text_lines(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.text_lines)
+    }
+
+    /// Get the next line which contains an entry
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    pub(crate) fn next_entry_line(&mut self) -> Option<Result<EntryLine, Error>> {
+        let (line_number, raw) = match self.pending.take() {
+            Some(pending) => pending,
+            None => match self.read_entry_line() {
+                Ok(Some(entry)) => entry,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            },
+        };
+
+        // A cedict line is composed using the format below
+        // <traditional_chinese> <simplified_chinese> <pinyin> <translations>
+        let line = strip_line_ending(&raw).to_string();
+        let item = Item::parse_line(&line);
+
+        Some(Ok(EntryLine {
+            line_number,
+            line,
+            raw,
+            item,
+        }))
+    }
+
+    /// Read the lines until a line containing an entry is found. The metadata lines which are read
+    /// are added to the metadata and the comments are skipped. The raw line is returned with its line ending.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    fn read_entry_line(&mut self) -> Result<Option<(usize, String)>, Error> {
+        loop {
+            let mut raw = String::new();
+            if self.reader.read_line(&mut raw)? == 0 {
+                return Ok(None);
+            }
+
+            self.line_number += 1;
+
+            let line = strip_line_ending(&raw);
+            self.hasher.update(line.as_bytes());
+            self.hasher.update(b"\n");
+
+            if !line.starts_with(VALID_LINE_FILTER) {
+                return Ok(Some((self.line_number, raw)));
+            }
+
+            if line.starts_with(METADATA_PREFIX) {
+                if line.starts_with(ENTRIES_METADATA) {
+                    self.entries_line = Some((self.line_number, line.to_string()));
+                }
+
+                self.metadata.parse_line(line);
+            }
+
+            if self.keep_text_lines {
+                self.text_lines.push(raw);
+            }
+        }
+    }
+}
+
+/// Remove the line ending of a raw line whether it's `\n` or `\r\n`
+///
+/// # Arguments
+///
+/// * `raw` - &str
+fn strip_line_ending(raw: &str) -> &str {
+    let line = raw.strip_suffix('\n').unwrap_or(raw);
+
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+impl<R: Read> Iterator for CedictReader<R> {
+    type Item = Result<Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry_line().map(|entry| {
+            let entry = entry?;
+
+            entry.item.map_err(|err| Error::ParseLine {
+                line: entry.line_number,
+                column: err.column,
+                reason: err.reason,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn expect_to_stream_entries() {
+        let file = File::open("../static/cedict_sample_ts.u8").unwrap();
+        let reader = CedictReader::new(file).unwrap();
+
+        let entries = reader.metadata().entries;
+        let items = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(Some(items.len()), entries);
+        assert_eq!(items[0].traditional_character, "她");
+    }
+
+    #[test]
+    fn expect_to_continue_after_malformed_line() {
+        let file = File::open("../static/cedict_malformed_ts.u8").unwrap();
+        let results = CedictReader::new(file).unwrap().collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(Error::ParseLine { line: 3, .. })));
+        assert!(results[2].is_ok());
+    }
+}
@@ -0,0 +1,152 @@
+use super::{Dictionary, Item, Reference, ReferenceKind};
+use crate::variant::KeyVariant;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// LinkedEntry is a reference of an item with the entry that the reference point to.
+/// The entry is `None` when the reference could not be resolved.
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkedEntry<'a> {
+    pub kind: ReferenceKind,
+    pub reference: Reference,
+    pub entry: Option<&'a Item>,
+}
+
+/// DanglingReference is a reference which point to an entry that does not exist in the dictionary
+#[derive(Debug, Clone, Serialize)]
+pub struct DanglingReference<'a> {
+    pub item: &'a Item,
+    pub kind: ReferenceKind,
+    pub reference: Reference,
+}
+
+impl Dictionary {
+    /// Resolve a reference to the entry that it point to. The entry is matched on the characters and
+    /// on the pinyin when the reference define one.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `reference` - &Reference
+    pub fn resolve_reference(&self, reference: &Reference) -> Option<&Item> {
+        let headword = match self.key_variant {
+            KeyVariant::Simplified => &reference.simplified_character,
+            KeyVariant::Traditional => &reference.traditional_character,
+        };
+
+        let pinyin = reference.pinyin_tone_number.join(" ").to_lowercase();
+
+        self.get(headword).into_iter().find(|item| {
+            item.traditional_character == reference.traditional_character
+                && item.simplified_character == reference.simplified_character
+                && (pinyin.is_empty() || item.pinyin_tone_number.join(" ").to_lowercase() == pinyin)
+        })
+    }
+
+    /// Get the references of an item alongside with the entries that they point to
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `item` - &Item
+    pub fn linked_entries(&self, item: &Item) -> Vec<LinkedEntry<'_>> {
+        item.senses()
+            .into_iter()
+            .filter_map(|sense| {
+                sense.reference().map(|(kind, reference)| LinkedEntry {
+                    kind: *kind,
+                    reference: reference.to_owned(),
+                    entry: self.resolve_reference(reference),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the canonical entry of an item by walking through the variant references e.g:
+    /// 甚末 -> 甚麼 -> 什麼. The item itself is returned when it's not a variant of an other entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `item` - &Item
+    pub fn canonical_entry<'a>(&'a self, item: &'a Item) -> &'a Item {
+        let mut current = item;
+        let mut visited = HashSet::new();
+
+        // The visited set prevent to loop forever when two entries are variant of each other.
+        while visited.insert(std::ptr::from_ref(current)) {
+            let next = self
+                .linked_entries(current)
+                .into_iter()
+                .filter(|link| {
+                    matches!(
+                        link.kind,
+                        ReferenceKind::Variant | ReferenceKind::OldVariant
+                    )
+                })
+                .find_map(|link| link.entry);
+
+            match next {
+                Some(entry) => current = entry,
+                None => break,
+            }
+        }
+
+        current
+    }
+
+    /// List every references of the dictionary which could not be resolved
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub fn dangling_references(&self) -> Vec<DanglingReference<'_>> {
+        self.items
+            .iter()
+            .flat_map(|item| {
+                self.linked_entries(item)
+                    .into_iter()
+                    .filter(|link| link.entry.is_none())
+                    .map(move |link| DanglingReference {
+                        item,
+                        kind: link.kind,
+                        reference: link.reference,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn load_dictionary() -> Dictionary {
+        Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn expect_to_get_canonical_entry() {
+        let dictionary = load_dictionary();
+        let item = dictionary.get("甚末")[0];
+
+        let canonical = dictionary.canonical_entry(item);
+        assert_eq!(canonical.traditional_character, "什麼");
+    }
+
+    #[test]
+    fn expect_to_list_dangling_references() {
+        let dictionary = load_dictionary();
+
+        let dangling = dictionary.dangling_references();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].item.traditional_character, "北大");
+        assert_eq!(dangling[0].kind, ReferenceKind::Abbreviation);
+        assert_eq!(dangling[0].reference.traditional_character, "北京大學");
+    }
+}
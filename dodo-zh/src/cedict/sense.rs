@@ -0,0 +1,276 @@
+use serde::Serialize;
+
+// Constant
+const CLASSIFIER_PREFIX: &str = "CL:";
+const CLASSIFIER_SEPARATOR: char = ',';
+const REFERENCE_SEPARATOR: char = '|';
+const SURNAME_PREFIX: &str = "surname ";
+const REFERENCE_PREFIXES: [(&str, ReferenceKind); 6] = [
+    ("old variant of ", ReferenceKind::OldVariant),
+    ("variant of ", ReferenceKind::Variant),
+    ("Japanese variant of ", ReferenceKind::Variant),
+    ("abbr. for ", ReferenceKind::Abbreviation),
+    ("see also ", ReferenceKind::See),
+    ("see ", ReferenceKind::See),
+];
+const TAG_MARKERS: [(&str, SenseTag); 9] = [
+    ("(Tw)", SenseTag::Taiwan),
+    ("(dialect)", SenseTag::Dialect),
+    ("(Cantonese)", SenseTag::Cantonese),
+    ("(coll.)", SenseTag::Colloquial),
+    ("(slang)", SenseTag::Slang),
+    ("(literary)", SenseTag::Literary),
+    ("(archaic)", SenseTag::Archaic),
+    ("(old)", SenseTag::Old),
+    ("(classical)", SenseTag::Classical),
+];
+const LABEL_MARKERS: [(&str, SenseLabel); 2] = [
+    ("(lit.)", SenseLabel::Literal),
+    ("(fig.)", SenseLabel::Figurative),
+];
+
+/// Sense is a structured view of a single translation of a cedict entry e.g: `(Tw) (fig.) to finish`.
+/// The raw translation is kept as it is in the `raw` field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Sense {
+    pub raw: String,
+    pub gloss: String,
+    pub kind: SenseKind,
+    pub classifiers: Vec<Reference>,
+    pub tags: Vec<SenseTag>,
+    pub labels: Vec<SenseLabel>,
+}
+
+/// SenseKind describe what a translation is about
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SenseKind {
+    Definition,
+    Classifier,
+    Surname(String),
+    Reference(ReferenceKind, Reference),
+}
+
+/// ReferenceKind is the relation that a translation has with the referenced entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum ReferenceKind {
+    Variant,
+    OldVariant,
+    Abbreviation,
+    See,
+}
+
+/// Reference point to an other headword with the format `<traditional>|<simplified>[<pinyin>]` e.g: `個|个[ge4]`.
+/// The simplified character is equal to the traditional character when both are the same e.g: `著[zhuo2]`.
+/// The pinyin may be empty when the reference does not define it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Reference {
+    pub traditional_character: String,
+    pub simplified_character: String,
+    pub pinyin_tone_number: Vec<String>,
+}
+
+/// SenseTag are the register or region markers of a translation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SenseTag {
+    Taiwan,
+    Dialect,
+    Cantonese,
+    Colloquial,
+    Slang,
+    Literary,
+    Archaic,
+    Old,
+    Classical,
+}
+
+/// SenseLabel tell whether the translation is the literal or the figurative meaning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SenseLabel {
+    Literal,
+    Figurative,
+}
+
+impl Sense {
+    /// Parse a raw cedict translation into a sense
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - S
+    pub fn parse<S: AsRef<str>>(raw: S) -> Self {
+        let raw = raw.as_ref();
+        let mut gloss = raw.to_string();
+
+        let tags = extract_markers(&mut gloss, &TAG_MARKERS);
+        let labels = extract_markers(&mut gloss, &LABEL_MARKERS);
+
+        let mut sense = Sense {
+            raw: raw.to_string(),
+            gloss,
+            kind: SenseKind::Definition,
+            classifiers: Vec::new(),
+            tags,
+            labels,
+        };
+
+        if let Some(classifiers) = sense.gloss.strip_prefix(CLASSIFIER_PREFIX) {
+            sense.classifiers = classifiers
+                .split(CLASSIFIER_SEPARATOR)
+                .filter_map(|classifier| Reference::parse(classifier).map(|(r, _)| r))
+                .collect();
+            sense.kind = SenseKind::Classifier;
+
+            return sense;
+        }
+
+        if let Some(name) = sense.gloss.strip_prefix(SURNAME_PREFIX) {
+            sense.kind = SenseKind::Surname(name.trim().to_string());
+
+            return sense;
+        }
+
+        for (prefix, kind) in REFERENCE_PREFIXES {
+            if let Some((reference, _)) = sense
+                .gloss
+                .strip_prefix(prefix)
+                .and_then(|rest| Reference::parse(rest))
+            {
+                sense.kind = SenseKind::Reference(kind, reference);
+                break;
+            }
+        }
+
+        sense
+    }
+
+    /// Return the referenced entry if the sense point to an other entry
+    pub fn reference(&self) -> Option<(&ReferenceKind, &Reference)> {
+        match &self.kind {
+            SenseKind::Reference(kind, reference) => Some((kind, reference)),
+            _ => None,
+        }
+    }
+}
+
+impl Reference {
+    /// Parse a reference at the beginning of the input e.g: `個|个[ge4], and more`. The rest of the input
+    /// which follow the reference is returned alongside the reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - &str
+    pub(crate) fn parse(input: &str) -> Option<(Self, &str)> {
+        let input = input.trim_start();
+        let end = input
+            .find(|c: char| c == '[' || c == ',' || c == ';' || c == ')' || c.is_whitespace())
+            .unwrap_or(input.len());
+
+        let characters = &input[..end];
+        // A reference is only made of chinese characters.
+        if characters.is_empty()
+            || characters
+                .chars()
+                .any(|c| c.is_ascii() && c != REFERENCE_SEPARATOR)
+        {
+            return None;
+        }
+
+        let (traditional, simplified) = characters
+            .split_once(REFERENCE_SEPARATOR)
+            .unwrap_or((characters, characters));
+
+        let mut rest = &input[end..];
+        let mut pinyin = Vec::new();
+        if let Some((content, remaining)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            pinyin = content.split_whitespace().map(|p| p.to_string()).collect();
+            rest = remaining;
+        }
+
+        Some((
+            Reference {
+                traditional_character: traditional.to_string(),
+                simplified_character: simplified.to_string(),
+                pinyin_tone_number: pinyin,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Remove the markers found in the gloss and return the value associated to the markers
+///
+/// # Arguments
+///
+/// * `gloss` - &mut String
+/// * `markers` - &[(&str, T)]
+fn extract_markers<T: Copy>(gloss: &mut String, markers: &[(&str, T)]) -> Vec<T> {
+    let mut found = Vec::new();
+    for (marker, value) in markers {
+        if gloss.contains(marker) {
+            *gloss = gloss.replace(marker, "");
+            found.push(*value);
+        }
+    }
+
+    if !found.is_empty() {
+        *gloss = gloss.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_to_parse_classifiers() {
+        let sense = Sense::parse("CL:個|个[ge4],位[wei4]");
+
+        assert_eq!(sense.kind, SenseKind::Classifier);
+        assert_eq!(sense.classifiers.len(), 2);
+        assert_eq!(sense.classifiers[0].traditional_character, "個");
+        assert_eq!(sense.classifiers[0].simplified_character, "个");
+        assert_eq!(sense.classifiers[0].pinyin_tone_number, vec!["ge4"]);
+        assert_eq!(sense.classifiers[1].simplified_character, "位");
+    }
+
+    #[test]
+    fn expect_to_parse_references() {
+        let sense = Sense::parse("abbr. for 北京大學|北京大学[Bei3 jing1 Da4 xue2]");
+
+        let (kind, reference) = sense.reference().unwrap();
+        assert_eq!(kind, &ReferenceKind::Abbreviation);
+        assert_eq!(reference.simplified_character, "北京大学");
+        assert_eq!(
+            reference.pinyin_tone_number,
+            vec!["Bei3", "jing1", "Da4", "xue2"]
+        );
+
+        let sense = Sense::parse("old variant of 著[zhuo2]");
+        let (kind, reference) = sense.reference().unwrap();
+        assert_eq!(kind, &ReferenceKind::OldVariant);
+        assert_eq!(reference.traditional_character, "著");
+
+        let sense = Sense::parse("see 什麼|什么[shen2 me5]");
+        let (kind, reference) = sense.reference().unwrap();
+        assert_eq!(kind, &ReferenceKind::See);
+        assert_eq!(reference.simplified_character, "什么");
+    }
+
+    #[test]
+    fn expect_to_extract_tags_and_labels() {
+        let sense = Sense::parse("(Tw) (fig.) to finish; to complete");
+
+        assert_eq!(sense.tags, vec![SenseTag::Taiwan]);
+        assert_eq!(sense.labels, vec![SenseLabel::Figurative]);
+        assert_eq!(sense.gloss, "to finish; to complete");
+        assert_eq!(sense.raw, "(Tw) (fig.) to finish; to complete");
+    }
+
+    #[test]
+    fn expect_to_parse_surname() {
+        let sense = Sense::parse("surname Li");
+
+        assert_eq!(sense.kind, SenseKind::Surname("Li".to_string()));
+    }
+}
@@ -0,0 +1,285 @@
+use super::reader::CedictReader;
+use super::writer::LayoutLine;
+use super::{
+    Dictionary, DictionaryMetadata, EnglishIndex, GlossLanguage, Item, PinyinIndex, PrefixIndex,
+};
+use crate::error::Error;
+use crate::variant::KeyVariant;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::OnceLock;
+
+// Constant
+const MAGIC: &[u8; 8] = b"DODOSNAP";
+// The version is only bumped when the payload change after a release of the crate
+const FORMAT_VERSION: u32 = 1;
+// magic + format version + source checksum + payload checksum + payload length
+const HEADER_LENGTH: usize = 8 + 4 + 4 + 4 + 8;
+
+/// SnapshotHeader is the header of a binary snapshot of a dictionary. The header has a fixed size and
+/// is written in little endian before the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub format_version: u32,
+    pub source_checksum: u32,
+    pub payload_checksum: u32,
+    pub payload_length: u64,
+}
+
+/// Payload written in the snapshot. Indexes are borrowed from the dictionary in order to avoid cloning them.
+#[derive(Serialize)]
+struct SnapshotPayloadRef<'a> {
+    metadata: &'a DictionaryMetadata,
+    key_variant: &'a KeyVariant,
+    language: &'a GlossLanguage,
+    items: &'a [Item],
+    simplified_headwords: &'a HashMap<String, Vec<usize>>,
+    traditional_headwords: &'a HashMap<String, Vec<usize>>,
+    layout: &'a [LayoutLine],
+    english_index: &'a EnglishIndex,
+    pinyin_index: &'a PinyinIndex,
+    simplified_prefix_index: &'a PrefixIndex,
+    traditional_prefix_index: &'a PrefixIndex,
+}
+
+/// Payload read from the snapshot
+#[derive(Deserialize)]
+struct SnapshotPayload {
+    metadata: DictionaryMetadata,
+    key_variant: KeyVariant,
+    language: GlossLanguage,
+    items: Vec<Item>,
+    simplified_headwords: HashMap<String, Vec<usize>>,
+    traditional_headwords: HashMap<String, Vec<usize>>,
+    layout: Vec<LayoutLine>,
+    english_index: EnglishIndex,
+    pinyin_index: PinyinIndex,
+    simplified_prefix_index: PrefixIndex,
+    traditional_prefix_index: PrefixIndex,
+}
+
+impl SnapshotHeader {
+    /// Read the header at the beginning of a snapshot
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - &[u8]
+    pub fn read(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LENGTH || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(Error::Snapshot(
+                "the content is not a dictionary snapshot".to_string(),
+            ));
+        }
+
+        let u32_at = |offset: usize| {
+            let mut buf = [0_u8; 4];
+            buf.copy_from_slice(&bytes[offset..offset + 4]);
+            u32::from_le_bytes(buf)
+        };
+
+        let mut payload_length = [0_u8; 8];
+        payload_length.copy_from_slice(&bytes[20..HEADER_LENGTH]);
+
+        Ok(Self {
+            format_version: u32_at(8),
+            source_checksum: u32_at(12),
+            payload_checksum: u32_at(16),
+            payload_length: u64::from_le_bytes(payload_length),
+        })
+    }
+
+    /// Check whether the snapshot has been built from an other cedict file than the given one by comparing
+    /// the checksum of the cedict file with the checksum stored in the header. The cedict file may be compressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `source` - R
+    pub fn is_stale<R: Read>(&self, source: R) -> Result<bool, Error> {
+        let checksum = CedictReader::new(source)?.into_checksum()?;
+
+        Ok(checksum != self.source_checksum)
+    }
+
+    /// Write the header
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `writer` - &mut W
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&self.format_version.to_le_bytes())?;
+        writer.write_all(&self.source_checksum.to_le_bytes())?;
+        writer.write_all(&self.payload_checksum.to_le_bytes())?;
+        writer.write_all(&self.payload_length.to_le_bytes())?;
+
+        Ok(())
+    }
+}
+
+impl Dictionary {
+    /// Write a versioned binary snapshot of the dictionary. The snapshot contains the entries, the metadata and
+    /// every indexes. Hence loading the snapshot does not require to parse the cedict file or to build the indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `writer` - W
+    pub fn save_snapshot<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        let payload = bincode::serialize(&SnapshotPayloadRef {
+            metadata: &self.metadata,
+            key_variant: &self.key_variant,
+            language: &self.language,
+            items: &self.items,
+            simplified_headwords: &self.simplified_headwords,
+            traditional_headwords: &self.traditional_headwords,
+            layout: &self.layout,
+            english_index: self
+                .english_index
+                .get_or_init(|| EnglishIndex::new(&self.items)),
+            pinyin_index: self
+                .pinyin_index
+                .get_or_init(|| PinyinIndex::new(&self.items)),
+            simplified_prefix_index: self.prefix_index(&KeyVariant::Simplified),
+            traditional_prefix_index: self.prefix_index(&KeyVariant::Traditional),
+        })?;
+
+        SnapshotHeader {
+            format_version: FORMAT_VERSION,
+            source_checksum: self.source_checksum,
+            payload_checksum: crc32fast::hash(&payload),
+            payload_length: payload.len() as u64,
+        }
+        .write(&mut writer)?;
+
+        writer.write_all(&payload)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Load a dictionary from a binary snapshot. The payload is deserialized into an owned dictionary
+    /// hence the bytes are not borrowed once the dictionary is loaded. The indexes are not rebuilt.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - &[u8]
+    pub fn load_snapshot(bytes: &[u8]) -> Result<Dictionary, Error> {
+        let header = SnapshotHeader::read(bytes)?;
+        if header.format_version != FORMAT_VERSION {
+            return Err(Error::Snapshot(format!(
+                "unsupported snapshot version {}, expected version {FORMAT_VERSION}",
+                header.format_version
+            )));
+        }
+
+        let payload = usize::try_from(header.payload_length)
+            .ok()
+            .and_then(|length| bytes.get(HEADER_LENGTH..HEADER_LENGTH.checked_add(length)?))
+            .ok_or_else(|| Error::Snapshot("the snapshot is truncated".to_string()))?;
+
+        if crc32fast::hash(payload) != header.payload_checksum {
+            return Err(Error::Snapshot("the snapshot is corrupted".to_string()));
+        }
+
+        let payload: SnapshotPayload = bincode::deserialize(payload)?;
+
+        Ok(Dictionary {
+            metadata: payload.metadata,
+            items: payload.items,
+            simplified_headwords: payload.simplified_headwords,
+            traditional_headwords: payload.traditional_headwords,
+            key_variant: payload.key_variant,
+            language: payload.language,
+            source_checksum: header.source_checksum,
+            layout: payload.layout,
+            english_index: OnceLock::from(payload.english_index),
+            pinyin_index: OnceLock::from(payload.pinyin_index),
+            simplified_prefix_index: OnceLock::from(payload.simplified_prefix_index),
+            traditional_prefix_index: OnceLock::from(payload.traditional_prefix_index),
+            // The polyphone index depend on the frequencies hence it's rebuilt on the first call
+            polyphone_index: OnceLock::new(),
+        })
+    }
+
+    /// Load a dictionary from a binary snapshot which must have been built from the given cedict file.
+    /// An error is returned when the cedict file has changed since the snapshot has been built.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - &[u8]
+    /// * `source` - R
+    pub fn load_snapshot_for<R: Read>(bytes: &[u8], source: R) -> Result<Dictionary, Error> {
+        if SnapshotHeader::read(bytes)?.is_stale(source)? {
+            return Err(Error::Snapshot(
+                "the snapshot has been built from an other cedict file".to_string(),
+            ));
+        }
+
+        Dictionary::load_snapshot(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn expect_to_roundtrip_snapshot() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let mut snapshot = Vec::new();
+        dictionary.save_snapshot(&mut snapshot).unwrap();
+
+        let header = SnapshotHeader::read(&snapshot).unwrap();
+        assert_eq!(header.source_checksum, dictionary.source_checksum());
+
+        let loaded = Dictionary::load_snapshot(&snapshot).unwrap();
+        assert_eq!(loaded.len(), dictionary.len());
+        assert_eq!(loaded.metadata, dictionary.metadata);
+        assert_eq!(loaded.get("了").len(), 2);
+        assert_eq!(
+            loaded.search_translation("friend")[0]
+                .item
+                .traditional_character,
+            "友"
+        );
+    }
+
+    #[test]
+    fn expect_to_reject_corrupted_snapshot() {
+        let dictionary = Dictionary::from_str("她 她 [ta1] /she/", KeyVariant::Simplified).unwrap();
+
+        let mut snapshot = Vec::new();
+        dictionary.save_snapshot(&mut snapshot).unwrap();
+
+        let last = snapshot.len() - 1;
+        snapshot[last] ^= 0xff;
+        assert!(Dictionary::load_snapshot(&snapshot).is_err());
+        assert!(Dictionary::load_snapshot(b"not a snapshot").is_err());
+    }
+
+    #[test]
+    fn expect_to_detect_stale_snapshot() {
+        let content = "她 她 [ta1] /she/\n";
+        let dictionary = Dictionary::from_str(content, KeyVariant::Simplified).unwrap();
+
+        let mut snapshot = Vec::new();
+        dictionary.save_snapshot(&mut snapshot).unwrap();
+
+        let header = SnapshotHeader::read(&snapshot).unwrap();
+        assert!(!header.is_stale(content.as_bytes()).unwrap());
+        assert!(Dictionary::load_snapshot_for(&snapshot, content.as_bytes()).is_ok());
+
+        let updated = "她 她 [ta1] /she/\n他 他 [ta1] /he/\n";
+        assert!(header.is_stale(updated.as_bytes()).unwrap());
+        assert!(Dictionary::load_snapshot_for(&snapshot, updated.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,523 @@
+use super::{Diagnostic, DiagnosticKind, Dictionary, Item};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
+
+// Constant
+static SYLLABLES: OnceLock<HashSet<&'static str>> = OnceLock::new();
+const BRACKETS: [(char, char); 2] = [('(', ')'), ('[', ']')];
+
+/// Severity is the level of an issue. An error should prevent the dictionary to be shipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Rule is a check which is done on the entries of a dictionary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// The line could not be parsed when loading the dictionary
+    Parse,
+    /// The number of entries declared by the `#! entries=` header is out of date
+    EntriesHeader,
+    /// The number of pinyin syllables does not match the number of characters
+    SyllableCount,
+    /// The pinyin is not a mandarin syllable e.g: xe4
+    InvalidPinyin,
+    /// The tone number is missing or is outside of 1-5
+    ToneNumber,
+    /// The simplified headword use a character which only exist in traditional chinese e.g: 摯友 摯友
+    VariantMismatch,
+    /// The same entry is defined several times
+    DuplicateEntry,
+    /// The entry has no translations or an empty translation
+    EmptyGloss,
+    /// The parentheses or the square brackets of a translation are not balanced
+    UnbalancedBrackets,
+}
+
+/// Issue is a problem found by a rule on an entry of the dictionary
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub line_number: Option<usize>,
+    pub entry: String,
+    pub message: String,
+}
+
+/// ValidationReport is the list of issues found in a dictionary
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+/// Validator check the entries of a dictionary with a set of rules. Each rule has a severity
+/// which can be changed. A rule can also be disabled.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::{Dictionary, Rule, Severity, Validator};
+/// use dodo_zh::variant::KeyVariant;
+///
+/// let dictionary = Dictionary::from_str("朋友 朋友 [peng2] /friend/", KeyVariant::Simplified).unwrap();
+/// let report = Validator::default()
+///     .with_severity(Rule::SyllableCount, Severity::Warning)
+///     .validate(&dictionary);
+///
+/// assert_eq!(report.warnings(), 1);
+/// assert!(!report.has_errors());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Validator {
+    severities: HashMap<Rule, Severity>,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        let severities = HashMap::from([
+            (Rule::Parse, Severity::Error),
+            (Rule::EntriesHeader, Severity::Warning),
+            (Rule::SyllableCount, Severity::Error),
+            (Rule::InvalidPinyin, Severity::Error),
+            (Rule::ToneNumber, Severity::Error),
+            (Rule::VariantMismatch, Severity::Warning),
+            (Rule::DuplicateEntry, Severity::Warning),
+            (Rule::EmptyGloss, Severity::Error),
+            (Rule::UnbalancedBrackets, Severity::Warning),
+        ]);
+
+        Self { severities }
+    }
+}
+
+impl Validator {
+    /// Change the severity of a rule. A disabled rule is enabled again
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `rule` - Rule
+    /// * `severity` - Severity
+    pub fn with_severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.severities.insert(rule, severity);
+        self
+    }
+
+    /// Disable a rule
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `rule` - Rule
+    pub fn without_rule(mut self, rule: Rule) -> Self {
+        self.severities.remove(&rule);
+        self
+    }
+
+    /// Validate every entries of the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `dictionary` - &Dictionary
+    pub fn validate(&self, dictionary: &Dictionary) -> ValidationReport {
+        self.validate_with_diagnostics(dictionary, &[])
+    }
+
+    /// Validate every entries of the dictionary alongside with the diagnostics of a lenient loading.
+    /// The skipped lines are reported with the `Parse` rule and an out of date `#! entries=` header
+    /// with the `EntriesHeader` rule
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `dictionary` - &Dictionary
+    /// * `diagnostics` - &[Diagnostic]
+    pub fn validate_with_diagnostics(
+        &self,
+        dictionary: &Dictionary,
+        diagnostics: &[Diagnostic],
+    ) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        report
+            .issues
+            .extend(diagnostics.iter().filter_map(|diagnostic| {
+                let rule = match diagnostic.kind {
+                    DiagnosticKind::Parse => Rule::Parse,
+                    DiagnosticKind::EntriesHeader => Rule::EntriesHeader,
+                };
+
+                self.severities.get(&rule).map(|severity| Issue {
+                    rule,
+                    severity: *severity,
+                    line_number: Some(diagnostic.line_number),
+                    entry: diagnostic.line.clone(),
+                    message: diagnostic.reason.clone(),
+                })
+            }));
+
+        let line_numbers = dictionary.line_numbers();
+        let traditional_only = traditional_only_characters(dictionary);
+        let mut seen = HashSet::new();
+
+        for (idx, item) in dictionary.items().iter().enumerate() {
+            let mut issues = Vec::new();
+            check_syllable_count(item, &mut issues);
+            check_pinyin(item, &mut issues);
+            check_variant(item, &traditional_only, &mut issues);
+            check_glosses(item, &mut issues);
+
+            if !seen.insert(item.to_string()) {
+                issues.push((
+                    Rule::DuplicateEntry,
+                    "The entry is defined several times".to_string(),
+                ));
+            }
+
+            report
+                .issues
+                .extend(issues.into_iter().filter_map(|(rule, message)| {
+                    self.severities.get(&rule).map(|severity| Issue {
+                        rule,
+                        severity: *severity,
+                        line_number: line_numbers.get(idx).copied().flatten(),
+                        entry: item.to_string(),
+                        message,
+                    })
+                }));
+        }
+
+        report
+    }
+}
+
+impl ValidationReport {
+    /// Return the number of issues with the error severity
+    pub fn errors(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Return the number of issues with the warning severity
+    pub fn warnings(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    /// Check whether the report contains an error
+    pub fn has_errors(&self) -> bool {
+        self.errors() > 0
+    }
+
+    /// Count the issues of a severity
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `severity` - Severity
+    fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+}
+
+/// Check that there is a pinyin syllable for each character of the headwords
+///
+/// # Arguments
+///
+/// * `item` - &Item
+/// * `issues` - &mut Vec<(Rule, String)>
+fn check_syllable_count(item: &Item, issues: &mut Vec<(Rule, String)>) {
+    let syllables = item.pinyin_tone_number.len();
+
+    for headword in [&item.traditional_character, &item.simplified_character] {
+        let characters = headword.chars().count();
+        if characters != syllables {
+            issues.push((
+                Rule::SyllableCount,
+                format!("{headword} has {characters} characters but {syllables} pinyin syllables"),
+            ));
+            return;
+        }
+    }
+}
+
+/// Check that each pinyin is a mandarin syllable with a tone between 1 and 5. Latin letters e.g: `K`
+/// and punctuations e.g: `,` are skipped
+///
+/// # Arguments
+///
+/// * `item` - &Item
+/// * `issues` - &mut Vec<(Rule, String)>
+fn check_pinyin(item: &Item, issues: &mut Vec<(Rule, String)>) {
+    let syllables = SYLLABLES.get_or_init(|| {
+        include_str!("../static/pinyin_syllables.txt")
+            .split_whitespace()
+            .collect()
+    });
+
+    for pinyin in &item.pinyin_tone_number {
+        let letters = pinyin.trim_end_matches(|c: char| c.is_ascii_digit());
+        let tone = &pinyin[letters.len()..];
+
+        if tone.is_empty() {
+            let is_latin_letter = letters.chars().all(|c| c.is_ascii_uppercase());
+            let is_punctuation = !letters.chars().any(char::is_alphabetic);
+            if !is_latin_letter && !is_punctuation {
+                issues.push((
+                    Rule::ToneNumber,
+                    format!("{pinyin} does not have a tone number"),
+                ));
+            }
+
+            continue;
+        }
+
+        if !matches!(tone, "1" | "2" | "3" | "4" | "5") {
+            issues.push((
+                Rule::ToneNumber,
+                format!("{pinyin} has the tone {tone} which is outside of 1-5"),
+            ));
+        }
+
+        let normalized = letters.to_lowercase().replace("u:", "v").replace('ü', "v");
+        if !syllables.contains(normalized.as_str()) {
+            issues.push((
+                Rule::InvalidPinyin,
+                format!("{pinyin} is not a mandarin syllable"),
+            ));
+        }
+    }
+}
+
+/// Check that the simplified headword does not use a character which only exist in traditional chinese.
+/// The traditional only characters are the single character entries which never have the same simplified form
+///
+/// # Arguments
+///
+/// * `item` - &Item
+/// * `traditional_only` - &HashMap<char, char>
+/// * `issues` - &mut Vec<(Rule, String)>
+fn check_variant(
+    item: &Item,
+    traditional_only: &HashMap<char, char>,
+    issues: &mut Vec<(Rule, String)>,
+) {
+    if item.traditional_character.chars().count() < 2 {
+        return;
+    }
+
+    for (traditional, simplified) in item
+        .traditional_character
+        .chars()
+        .zip(item.simplified_character.chars())
+    {
+        if let Some(expected) = traditional_only.get(&traditional)
+            && traditional == simplified
+        {
+            issues.push((
+                Rule::VariantMismatch,
+                format!("{traditional} should be simplified as {expected}"),
+            ));
+        }
+    }
+}
+
+/// Check that the entry has translations which are not empty and which have balanced brackets.
+/// The lines of the cantonese readings file are not expected to have translations.
+///
+/// # Arguments
+///
+/// * `item` - &Item
+/// * `issues` - &mut Vec<(Rule, String)>
+fn check_glosses(item: &Item, issues: &mut Vec<(Rule, String)>) {
+    if item.translations.is_empty() && item.jyutping.is_none() {
+        issues.push((
+            Rule::EmptyGloss,
+            "The entry has no translations".to_string(),
+        ));
+    }
+
+    for translation in &item.translations {
+        if translation.trim().is_empty() {
+            issues.push((
+                Rule::EmptyGloss,
+                "The entry has an empty translation".to_string(),
+            ));
+        }
+
+        for (open, close) in BRACKETS {
+            let mut depth = 0_i32;
+            for c in translation.chars() {
+                match c {
+                    c if c == open => depth += 1,
+                    c if c == close => depth -= 1,
+                    _ => {}
+                }
+
+                if depth < 0 {
+                    break;
+                }
+            }
+
+            if depth != 0 {
+                issues.push((
+                    Rule::UnbalancedBrackets,
+                    format!("{translation} has unbalanced {open}{close}"),
+                ));
+            }
+        }
+    }
+}
+
+/// Get the characters which only exist in traditional chinese with their simplified form e.g: 摯 -> 挚
+///
+/// # Arguments
+///
+/// * `dictionary` - &Dictionary
+fn traditional_only_characters(dictionary: &Dictionary) -> HashMap<char, char> {
+    let mut simplified_forms: HashMap<char, Option<char>> = HashMap::new();
+
+    for item in dictionary.items() {
+        let mut traditional = item.traditional_character.chars();
+        let mut simplified = item.simplified_character.chars();
+        if let (Some(t), None, Some(s), None) = (
+            traditional.next(),
+            traditional.next(),
+            simplified.next(),
+            simplified.next(),
+        ) {
+            let form = simplified_forms.entry(t).or_insert(Some(s));
+            // The character is also a simplified character e.g: 乾 乾 [qian2] and 乾 干 [gan1]
+            if t == s {
+                *form = None;
+            }
+        }
+    }
+
+    simplified_forms
+        .into_iter()
+        .filter_map(|(t, s)| s.filter(|s| *s != t).map(|s| (t, s)))
+        .collect()
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Rule::Parse => "parse",
+            Rule::EntriesHeader => "entries-header",
+            Rule::SyllableCount => "syllable-count",
+            Rule::InvalidPinyin => "invalid-pinyin",
+            Rule::ToneNumber => "tone-number",
+            Rule::VariantMismatch => "variant-mismatch",
+            Rule::DuplicateEntry => "duplicate-entry",
+            Rule::EmptyGloss => "empty-gloss",
+            Rule::UnbalancedBrackets => "unbalanced-brackets",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.severity, self.rule)?;
+        if let Some(line_number) = self.line_number {
+            write!(f, " line {line_number}")?;
+        }
+
+        write!(f, ": {}\n    {}", self.message, self.entry)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+
+        write!(f, "{} errors, {} warnings", self.errors(), self.warnings())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+    use std::path::PathBuf;
+
+    #[test]
+    fn expect_sample_to_be_valid() {
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let report = Validator::default().validate(&dictionary);
+        assert!(report.issues.is_empty(), "{report}");
+    }
+
+    #[test]
+    fn expect_to_report_issues() {
+        let content = "摯 挚 [zhi4] /sincere/\n\
+            摯友 摯友 [zhi4 you3] /close friend/\n\
+            學 学 [xe4] /to learn/\n\
+            好 好 [hao7] /good/\n\
+            朋友 朋友 [peng2] /friend (person/\n\
+            她 她 [ta1] /she/\n\
+            她 她 [ta1] /she/";
+        let dictionary = Dictionary::from_str(content, KeyVariant::Traditional).unwrap();
+
+        let report = Validator::default().validate(&dictionary);
+        let rules = report
+            .issues
+            .iter()
+            .map(|issue| (issue.rule, issue.line_number))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rules,
+            vec![
+                (Rule::VariantMismatch, Some(2)),
+                (Rule::InvalidPinyin, Some(3)),
+                (Rule::ToneNumber, Some(4)),
+                (Rule::SyllableCount, Some(5)),
+                (Rule::UnbalancedBrackets, Some(5)),
+                (Rule::DuplicateEntry, Some(7)),
+            ]
+        );
+        assert_eq!(report.errors(), 3);
+        assert_eq!(report.warnings(), 3);
+    }
+
+    #[test]
+    fn expect_outdated_entries_header_to_be_a_warning() {
+        let content = "#! entries=3\n她 她 [ta1] /she/\n";
+        let (dictionary, diagnostics) =
+            Dictionary::from_reader_lenient(content.as_bytes(), KeyVariant::Simplified).unwrap();
+
+        let report = Validator::default().validate_with_diagnostics(&dictionary, &diagnostics);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].rule, Rule::EntriesHeader);
+        assert_eq!(report.issues[0].line_number, Some(1));
+        assert_eq!(report.warnings(), 1);
+        assert!(!report.has_errors());
+    }
+}
@@ -0,0 +1,247 @@
+use super::{Dictionary, DictionaryMetadata, Item};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+
+// Constant
+const LINE_ENDING: &str = "\n";
+
+/// LayoutLine is a line of the cedict file which has been loaded. The layout keep the comments, the metadata
+/// and the order of the entries in order to write the dictionary back as it was read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum LayoutLine {
+    /// A comment, a metadata or a malformed line which is written as it is
+    Text(String),
+    /// An entry of the dictionary. The raw line is only kept when it differs from the formatted item
+    /// e.g: extra spaces or a `\r\n` line ending
+    Entry { item: usize, raw: Option<String> },
+}
+
+impl LayoutLine {
+    /// Create the layout line of an entry. The raw line is dropped when formatting the item produce the same line
+    ///
+    /// # Arguments
+    ///
+    /// * `item_idx` - usize
+    /// * `item` - &Item
+    /// * `raw` - String
+    pub(crate) fn entry(item_idx: usize, item: &Item, raw: String) -> Self {
+        let formatted = format!("{item}{LINE_ENDING}");
+
+        LayoutLine::Entry {
+            item: item_idx,
+            raw: (raw != formatted).then_some(raw),
+        }
+    }
+}
+
+/// CedictWriter write cedict lines to any writer e.g: a file or a buffer
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::{CedictWriter, Dictionary};
+/// use dodo_zh::variant::KeyVariant;
+///
+/// let content = "# comment\n她 她 [ta1] /she/\n";
+/// let dictionary = Dictionary::from_str(content, KeyVariant::Simplified).unwrap();
+///
+/// let mut writer = CedictWriter::new(Vec::new());
+/// writer.write_dictionary(&dictionary).unwrap();
+/// assert_eq!(writer.into_inner(), content.as_bytes());
+/// ```
+#[derive(Debug)]
+pub struct CedictWriter<W: Write> {
+    writer: W,
+    // Whether the last line which has been written is missing a line ending e.g: the last line of a file
+    pending_line_ending: bool,
+}
+
+impl<W: Write> CedictWriter<W> {
+    /// Create a new cedict writer
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - W
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pending_line_ending: false,
+        }
+    }
+
+    /// Write an item as a cedict line e.g: `朋友 朋友 [peng2 you5] /friend/`
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `item` - &Item
+    pub fn write_item(&mut self, item: &Item) -> Result<(), Error> {
+        self.write_raw(&format!("{item}{LINE_ENDING}"))
+    }
+
+    /// Write every lines of the dictionary. The header, the comments and the order of the entries
+    /// are preserved. Hence writing a dictionary which has not been modified produce the same content
+    /// as the loaded cedict file. The `#! entries=N` line of the header is updated with the number of
+    /// entries of the dictionary so that an edited dictionary can be loaded again.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `dictionary` - &Dictionary
+    pub fn write_dictionary(&mut self, dictionary: &Dictionary) -> Result<(), Error> {
+        for line in &dictionary.layout {
+            match line {
+                LayoutLine::Text(raw) => {
+                    match DictionaryMetadata::rewrite_entries_line(raw, dictionary.len()) {
+                        Some(line) => self.write_raw(&line)?,
+                        None => self.write_raw(raw)?,
+                    }
+                }
+                LayoutLine::Entry { raw: Some(raw), .. } => self.write_raw(raw)?,
+                LayoutLine::Entry { item, raw: None } => {
+                    self.write_item(&dictionary.items[*item])?
+                }
+            }
+        }
+
+        self.writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Consume the cedict writer and return the inner writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write a raw line. A line ending is added beforehand when the previous line did not have one
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `raw` - &str
+    fn write_raw(&mut self, raw: &str) -> Result<(), Error> {
+        if self.pending_line_ending {
+            self.writer.write_all(LINE_ENDING.as_bytes())?;
+        }
+
+        self.writer.write_all(raw.as_bytes())?;
+        self.pending_line_ending = !raw.ends_with('\n');
+
+        Ok(())
+    }
+}
+
+impl Dictionary {
+    /// Write the dictionary in the cedict format
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `writer` - W
+    pub fn write_cedict<W: Write>(&self, writer: W) -> Result<(), Error> {
+        CedictWriter::new(writer).write_dictionary(self)
+    }
+
+    /// Get the line number of each item in the cedict file as it would be written. Items which have been
+    /// removed from the layout do not have a line number
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub(crate) fn line_numbers(&self) -> Vec<Option<usize>> {
+        let mut line_numbers = vec![None; self.items.len()];
+        for (position, line) in self.layout.iter().enumerate() {
+            if let LayoutLine::Entry { item, .. } = line
+                && let Some(line_number) = line_numbers.get_mut(*item)
+            {
+                *line_number = Some(position + 1);
+            }
+        }
+
+        line_numbers
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} [{}]",
+            self.traditional_character,
+            self.simplified_character,
+            self.pinyin_tone_number.join(" ")
+        )?;
+
+        if let Some(jyutping) = &self.jyutping {
+            write!(f, " {{{}}}", jyutping.join(" "))?;
+        }
+
+        // Lines of the cantonese readings file do not have translations
+        match (&self.jyutping, self.translations.is_empty()) {
+            (Some(_), true) => Ok(()),
+            _ => write!(f, " /{}/", self.translations.join("/")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cedict::{Dictionary, Item};
+    use crate::variant::KeyVariant;
+
+    #[test]
+    fn expect_to_roundtrip_cedict_file() {
+        let content = std::fs::read("../static/cedict_sample_ts.u8").unwrap();
+        let dictionary = Dictionary::from_bytes(&content, KeyVariant::Traditional).unwrap();
+
+        let mut output = Vec::new();
+        dictionary.write_cedict(&mut output).unwrap();
+        assert_eq!(output, content);
+
+        // Line endings and malformed lines are kept as well
+        let content = "# comment\r\n她 她 [ta1] /she/\r\n我 我 wo3] /I/\n友 友 [you3]  /friend/";
+        let (dictionary, _) =
+            Dictionary::from_reader_lenient(content.as_bytes(), KeyVariant::Simplified).unwrap();
+
+        let mut output = Vec::new();
+        dictionary.write_cedict(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), content);
+    }
+
+    #[test]
+    fn expect_to_write_modified_dictionary() {
+        let content = "# comment\n她 她 [ta1] /she/\n了 了 [le5] /completed action marker/\n";
+        let mut dictionary = Dictionary::from_str(content, KeyVariant::Simplified).unwrap();
+
+        dictionary.retain(|item| item.traditional_character != "她");
+        dictionary.push(Item::try_from("友 友 [you3] /friend/".to_string()).unwrap());
+
+        let mut output = Vec::new();
+        dictionary.write_cedict(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "# comment\n了 了 [le5] /completed action marker/\n友 友 [you3] /friend/\n"
+        );
+        assert_eq!(dictionary.get("友").len(), 1);
+        assert!(!dictionary.contains("她"));
+    }
+
+    #[test]
+    fn expect_to_reload_edited_dictionary() {
+        let content = std::fs::read("../static/cedict_sample_ts.u8").unwrap();
+        let mut dictionary = Dictionary::from_bytes(&content, KeyVariant::Traditional).unwrap();
+
+        dictionary.retain(|item| item.traditional_character != "她");
+
+        let mut output = Vec::new();
+        dictionary.write_cedict(&mut output).unwrap();
+        assert!(String::from_utf8_lossy(&output).contains("#! entries=21\n"));
+
+        let reloaded = Dictionary::from_bytes(&output, KeyVariant::Traditional).unwrap();
+        assert_eq!(reloaded.len(), dictionary.len());
+        assert_eq!(reloaded.metadata.entries, Some(dictionary.len()));
+    }
+}
@@ -0,0 +1,602 @@
+use crate::cedict::{Dictionary, Item};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+// Constant
+const UNKNOWN_COMPONENT: &str = "？";
+const IDS_START: char = '^';
+const IDS_END: char = '$';
+// Ideographic description characters which take three components e.g: ⿲ and ⿳
+const TERNARY_OPERATORS: [char; 2] = ['\u{2FF2}', '\u{2FF3}'];
+// Ideographic description characters which take a single component e.g: ⿾ and ⿿
+const UNARY_OPERATORS: [char; 2] = ['\u{2FFE}', '\u{2FFF}'];
+// Depth above which a decomposition tree is not expanded anymore. It protects against cycles in the files
+const MAX_TREE_DEPTH: usize = 16;
+
+/// Decomposition is the tree of the components of a character e.g: 語 = ⿰(言, ⿱(五, 口)).
+/// A node is either a component or an ideographic description character with its operands.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Decomposition {
+    /// The character of the node. An intermediate composition or an unknown component does not have one
+    pub component: Option<String>,
+    /// The ideographic description character which arrange the children e.g: ⿰ for left to right
+    pub operator: Option<char>,
+    pub children: Vec<Decomposition>,
+}
+
+/// EtymologyKind is how a character has been formed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EtymologyKind {
+    Ideographic,
+    Pictographic,
+    Pictophonetic,
+}
+
+/// Etymology is the hint given to learners on how the character has been formed. A pictophonetic
+/// character has a semantic component which give the meaning and a phonetic component which give the sound
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Etymology {
+    #[serde(rename = "type")]
+    pub kind: EtymologyKind,
+    pub hint: Option<String>,
+    pub phonetic: Option<String>,
+    pub semantic: Option<String>,
+}
+
+/// CharacterDecomposition is the decomposition of a character with the data of the Make Me a Hanzi
+/// dictionary when it's available
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CharacterDecomposition {
+    pub character: String,
+    /// The direct decomposition of the character. Atomic characters e.g: 木 do not have one
+    pub decomposition: Option<Decomposition>,
+    pub radical: Option<String>,
+    pub definition: Option<String>,
+    pub pinyin: Vec<String>,
+    pub etymology: Option<Etymology>,
+}
+
+/// Line of the Make Me a Hanzi dictionary.txt file
+#[derive(Deserialize)]
+struct HanziLine {
+    character: String,
+    definition: Option<String>,
+    #[serde(default)]
+    pinyin: Vec<String>,
+    decomposition: String,
+    etymology: Option<Etymology>,
+    radical: Option<String>,
+}
+
+/// Decompositions is the list of the character decompositions loaded from an IDS file or from the
+/// Make Me a Hanzi dictionary.txt file. It has a reverse index which allows to find the characters
+/// containing a component e.g: every characters with 氵.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::decomposition::Decompositions;
+///
+/// let decompositions = Decompositions::from_reader("U+8A9E\t語\t^⿰言吾$(GHJKTV)\nU+543E\t吾\t⿱五口".as_bytes()).unwrap();
+///
+/// assert_eq!(decompositions.components("語"), vec!["言", "吾"]);
+/// assert_eq!(decompositions.characters_with_component("五"), vec!["吾", "語"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Decompositions {
+    characters: HashMap<String, CharacterDecomposition>,
+    // Component to the characters which directly use it
+    component_index: HashMap<String, BTreeSet<String>>,
+}
+
+impl Decompositions {
+    /// Load the decompositions of a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - &PathBuf
+    pub fn new(path: &PathBuf) -> Result<Decompositions, Error> {
+        Decompositions::from_reader(File::open(path)?)
+    }
+
+    /// Load the decompositions from any reader. Each line is either a JSON line of the Make Me a Hanzi
+    /// dictionary or an IDS line with the format `U+8A9E<TAB>語<TAB>^⿰言吾$(GHJKTV)`. When an IDS line
+    /// has several decompositions, the first one is used
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    pub fn from_reader<R: Read>(reader: R) -> Result<Decompositions, Error> {
+        let mut decompositions = Decompositions::default();
+
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            let decomposition = match line.starts_with('{') {
+                true => parse_hanzi_line(line),
+                false => parse_ids_line(line),
+            }
+            .map_err(|reason| Error::Decomposition(format!("line {}: {reason}", idx + 1)))?;
+
+            decompositions.insert(decomposition);
+        }
+
+        Ok(decompositions)
+    }
+
+    /// Get the decomposition of a character
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - S
+    pub fn get<S: AsRef<str>>(&self, character: S) -> Option<&CharacterDecomposition> {
+        self.characters.get(character.as_ref())
+    }
+
+    /// Get the direct components of a character e.g: 語 -> 言, 吾
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - S
+    pub fn components<S: AsRef<str>>(&self, character: S) -> Vec<&str> {
+        self.get(character)
+            .and_then(|character| character.decomposition.as_ref())
+            .map(Decomposition::leaves)
+            .unwrap_or_default()
+    }
+
+    /// Get the radical of a character from the Make Me a Hanzi dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - S
+    pub fn radical<S: AsRef<str>>(&self, character: S) -> Option<&str> {
+        self.get(character)?.radical.as_deref()
+    }
+
+    /// Get the whole decomposition tree of a character. The components which can be decomposed are
+    /// expanded until the atomic components e.g: 語 = ⿰(言, 吾 = ⿱(五, 口))
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - S
+    pub fn tree<S: AsRef<str>>(&self, character: S) -> Option<Decomposition> {
+        let decomposition = self.get(character.as_ref())?.decomposition.as_ref()?;
+
+        Some(Decomposition {
+            component: Some(character.as_ref().to_string()),
+            ..self.expand(decomposition, 0)
+        })
+    }
+
+    /// Get every characters which contain the component directly or through an other component
+    /// e.g: 木 -> 林, 森. The characters are sorted by codepoint
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `component` - S
+    pub fn characters_with_component<S: AsRef<str>>(&self, component: S) -> Vec<&str> {
+        let mut found = BTreeSet::new();
+        let mut queue = VecDeque::from([component.as_ref()]);
+
+        while let Some(component) = queue.pop_front() {
+            for character in self.component_index.get(component).into_iter().flatten() {
+                if found.insert(character.as_str()) {
+                    queue.push_back(character);
+                }
+            }
+        }
+
+        found.into_iter().collect()
+    }
+
+    /// Get every characters whose radical is the given radical e.g: 氵 -> 河
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `radical` - S
+    pub fn characters_with_radical<S: AsRef<str>>(&self, radical: S) -> Vec<&str> {
+        let mut characters = self
+            .characters
+            .values()
+            .filter(|character| character.radical.as_deref() == Some(radical.as_ref()))
+            .map(|character| character.character.as_str())
+            .collect::<Vec<_>>();
+
+        characters.sort_unstable();
+        characters
+    }
+
+    /// Return the number of characters
+    pub fn len(&self) -> usize {
+        self.characters.len()
+    }
+
+    /// Check whether there is no characters
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+
+    /// Insert a character decomposition and index its components
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `decomposition` - CharacterDecomposition
+    fn insert(&mut self, decomposition: CharacterDecomposition) {
+        if let Some(previous) = self.characters.get(&decomposition.character)
+            && let Some(tree) = &previous.decomposition
+        {
+            for component in tree.leaves() {
+                if let Some(characters) = self.component_index.get_mut(component) {
+                    characters.remove(&decomposition.character);
+                }
+            }
+        }
+
+        if let Some(tree) = &decomposition.decomposition {
+            for component in tree.leaves() {
+                self.component_index
+                    .entry(component.to_string())
+                    .or_default()
+                    .insert(decomposition.character.clone());
+            }
+        }
+
+        self.characters
+            .insert(decomposition.character.clone(), decomposition);
+    }
+
+    /// Expand the components of a decomposition with their own decomposition
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `decomposition` - &Decomposition
+    /// * `depth` - usize
+    fn expand(&self, decomposition: &Decomposition, depth: usize) -> Decomposition {
+        if decomposition.operator.is_some() {
+            return Decomposition {
+                component: decomposition.component.clone(),
+                operator: decomposition.operator,
+                children: decomposition
+                    .children
+                    .iter()
+                    .map(|child| self.expand(child, depth))
+                    .collect(),
+            };
+        }
+
+        let expanded = decomposition
+            .component
+            .as_ref()
+            .filter(|_| depth < MAX_TREE_DEPTH)
+            .and_then(|component| self.get(component))
+            .and_then(|character| character.decomposition.as_ref());
+
+        match expanded {
+            Some(tree) => Decomposition {
+                component: decomposition.component.clone(),
+                ..self.expand(tree, depth + 1)
+            },
+            None => decomposition.clone(),
+        }
+    }
+}
+
+impl Decomposition {
+    /// Parse an ideographic description sequence e.g: ⿰言吾. Components may be written as entities
+    /// e.g: &CDP-8BBF; or as numbers between braces e.g: {1}
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - &str
+    pub fn parse(ids: &str) -> Result<Decomposition, Error> {
+        parse_ids(ids).map_err(Error::Decomposition)
+    }
+
+    /// Get the components at the bottom of the decomposition in their order of appearance.
+    /// Unknown components are skipped
+    pub fn leaves(&self) -> Vec<&str> {
+        match (&self.operator, &self.component) {
+            (None, Some(component)) => vec![component.as_str()],
+            (None, None) => Vec::new(),
+            (Some(_), _) => self
+                .children
+                .iter()
+                .flat_map(Decomposition::leaves)
+                .collect(),
+        }
+    }
+
+    /// Create a leaf of the decomposition
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - &str
+    fn leaf(component: &str) -> Self {
+        Decomposition {
+            component: (component != UNKNOWN_COMPONENT).then(|| component.to_string()),
+            operator: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Parse an ideographic description sequence. The whole sequence has to be consumed
+///
+/// # Arguments
+///
+/// * `ids` - &str
+fn parse_ids(ids: &str) -> Result<Decomposition, String> {
+    let mut tokens = tokenize(ids).into_iter();
+    let decomposition = parse_tokens(&mut tokens).map_err(|reason| format!("{ids}: {reason}"))?;
+
+    match tokens.next() {
+        Some(token) => Err(format!(
+            "{ids}: unexpected component {token} after the end of the sequence"
+        )),
+        None => Ok(decomposition),
+    }
+}
+
+/// Split an ideographic description sequence into operators and components
+///
+/// # Arguments
+///
+/// * `ids` - &str
+fn tokenize(ids: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (idx, c) in ids.char_indices() {
+        match (start, c) {
+            (None, '&' | '{') => start = Some(idx),
+            (Some(begin), ';' | '}') => {
+                tokens.push(&ids[begin..idx + c.len_utf8()]);
+                start = None;
+            }
+            (Some(_), _) => {}
+            (None, _) => tokens.push(&ids[idx..idx + c.len_utf8()]),
+        }
+    }
+
+    tokens
+}
+
+/// Parse the tokens of an ideographic description sequence in prefix order
+///
+/// # Arguments
+///
+/// * `tokens` - &mut I
+fn parse_tokens<'a, I: Iterator<Item = &'a str>>(tokens: &mut I) -> Result<Decomposition, String> {
+    let token = tokens
+        .next()
+        .ok_or_else(|| "missing component at the end of the sequence".to_string())?;
+
+    let Some(operator) = token.chars().next().filter(|c| operand_count(*c) > 0) else {
+        return Ok(Decomposition::leaf(token));
+    };
+
+    let children = (0..operand_count(operator))
+        .map(|_| parse_tokens(tokens))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Decomposition {
+        component: None,
+        operator: Some(operator),
+        children,
+    })
+}
+
+/// Get the number of operands of an ideographic description character. Other characters do not have any
+///
+/// # Arguments
+///
+/// * `c` - char
+fn operand_count(c: char) -> usize {
+    match c {
+        c if TERNARY_OPERATORS.contains(&c) => 3,
+        c if UNARY_OPERATORS.contains(&c) => 1,
+        '\u{2FF0}'..='\u{2FFD}' | '\u{31EF}' => 2,
+        _ => 0,
+    }
+}
+
+/// Parse a line of the Make Me a Hanzi dictionary
+///
+/// # Arguments
+///
+/// * `line` - &str
+fn parse_hanzi_line(line: &str) -> Result<CharacterDecomposition, String> {
+    let hanzi: HanziLine = serde_json::from_str(line).map_err(|err| err.to_string())?;
+
+    let decomposition = match hanzi.decomposition.as_str() {
+        UNKNOWN_COMPONENT => None,
+        ids => Some(parse_ids(ids)?),
+    };
+
+    Ok(CharacterDecomposition {
+        character: hanzi.character,
+        decomposition,
+        radical: hanzi.radical,
+        definition: hanzi.definition,
+        pinyin: hanzi.pinyin,
+        etymology: hanzi.etymology,
+    })
+}
+
+/// Parse a line of an IDS file. The sequence may be surrounded with `^` and `$` followed by the sources
+/// e.g: ^⿰言吾$(GHJKTV)
+///
+/// # Arguments
+///
+/// * `line` - &str
+fn parse_ids_line(line: &str) -> Result<CharacterDecomposition, String> {
+    let mut columns = line.split('\t');
+    let (Some(_), Some(character), Some(ids)) = (columns.next(), columns.next(), columns.next())
+    else {
+        return Err("expected a codepoint, a character and a sequence".to_string());
+    };
+
+    let ids = ids.trim_start_matches(IDS_START);
+    let ids = ids.split(IDS_END).next().unwrap_or(ids);
+
+    // Atomic characters are described by themselves
+    let decomposition = match ids == character {
+        true => None,
+        false => Some(parse_ids(ids)?),
+    };
+
+    Ok(CharacterDecomposition {
+        character: character.to_string(),
+        decomposition,
+        radical: None,
+        definition: None,
+        pinyin: Vec::new(),
+        etymology: None,
+    })
+}
+
+impl Dictionary {
+    /// Get the entries of the characters which contain the component e.g: 氵 -> 河, 湖. Only the entries
+    /// of a single character are returned in the order of the cedict file
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `component` - S
+    /// * `decompositions` - &Decompositions
+    pub fn search_component<S: AsRef<str>>(
+        &self,
+        component: S,
+        decompositions: &Decompositions,
+    ) -> Vec<&Item> {
+        let characters = decompositions
+            .characters_with_component(component)
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        self.items()
+            .iter()
+            .filter(|item| {
+                characters.contains(item.traditional_character.as_str())
+                    || characters.contains(item.simplified_character.as_str())
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Decomposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(component) = &self.component {
+            write!(f, "{component}")?;
+        }
+
+        let Some(operator) = self.operator else {
+            return match self.component {
+                Some(_) => Ok(()),
+                None => write!(f, "{UNKNOWN_COMPONENT}"),
+            };
+        };
+
+        if self.component.is_some() {
+            write!(f, " = ")?;
+        }
+
+        write!(f, "{operator}(")?;
+        for (idx, child) in self.children.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{child}")?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+
+    #[test]
+    fn expect_to_decompose_with_make_me_a_hanzi() {
+        let decompositions =
+            Decompositions::new(&PathBuf::from("../static/makemeahanzi_sample.txt")).unwrap();
+
+        assert_eq!(decompositions.components("語"), vec!["言", "吾"]);
+        assert_eq!(decompositions.radical("河"), Some("氵"));
+        assert_eq!(
+            decompositions.tree("語").unwrap().to_string(),
+            "語 = ⿰(言, 吾 = ⿱(五, 口))"
+        );
+        assert!(decompositions.tree("木").is_none());
+
+        let etymology = decompositions.get("河").unwrap().etymology.clone().unwrap();
+        assert_eq!(etymology.kind, EtymologyKind::Pictophonetic);
+        assert_eq!(etymology.semantic.as_deref(), Some("氵"));
+        assert_eq!(etymology.phonetic.as_deref(), Some("可"));
+
+        assert_eq!(
+            decompositions.characters_with_component("木"),
+            vec!["林", "森"]
+        );
+        assert_eq!(
+            decompositions.characters_with_component("口"),
+            vec!["可", "吾", "河", "語"]
+        );
+        assert_eq!(
+            decompositions.characters_with_radical("木"),
+            vec!["木", "林", "森"]
+        );
+    }
+
+    #[test]
+    fn expect_to_decompose_with_ids() {
+        let decompositions =
+            Decompositions::new(&PathBuf::from("../static/ids_sample.txt")).unwrap();
+
+        assert!(decompositions.get("木").unwrap().decomposition.is_none());
+        assert_eq!(decompositions.components("森"), vec!["木", "木", "木"]);
+        assert_eq!(
+            decompositions.components("𠀋"),
+            vec!["丿", "&CDP-8BBF;", "一"]
+        );
+        assert_eq!(
+            decompositions.tree("湖").unwrap().to_string(),
+            "湖 = ⿰(氵, 胡 = ⿰(古, 月))"
+        );
+        assert!(Decomposition::parse("⿰言").is_err());
+        assert!(Decomposition::parse("⿰言吾口").is_err());
+
+        let dictionary = Dictionary::from_str(
+            "河 河 [he2] /river/\n湖 湖 [hu2] /lake/\n森林 森林 [sen1 lin2] /forest/\n林 林 [lin2] /woods/\n森 森 [sen1] /forest/",
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        let entries = dictionary.search_component("氵", &decompositions);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].simplified_character, "湖");
+        assert_eq!(dictionary.search_component("木", &decompositions).len(), 2);
+    }
+}
@@ -3,6 +3,14 @@ pub enum Error {
     Json(String),
     Io(String),
     Parse(String),
+    ParseLine {
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+    Snapshot(String),
+    Unihan(String),
+    Decomposition(String),
 }
 
 impl std::error::Error for Error {}
@@ -13,6 +21,17 @@ impl std::fmt::Display for Error {
             Self::Json(msg) => write!(f, "Unable to parse json file: {msg}"),
             Self::Io(msg) => write!(f, "Unable to process cedict: {msg}"),
             Self::Parse(msg) => write!(f, "Unable to parse cedict file {msg}"),
+            Self::ParseLine {
+                line,
+                column,
+                reason,
+            } => write!(
+                f,
+                "Unable to parse cedict file at line {line}, column {column}: {reason}"
+            ),
+            Self::Snapshot(msg) => write!(f, "Unable to process dictionary snapshot: {msg}"),
+            Self::Unihan(msg) => write!(f, "Unable to parse unihan file: {msg}"),
+            Self::Decomposition(msg) => write!(f, "Unable to parse decomposition: {msg}"),
         }
     }
 }
@@ -23,6 +42,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<bincode::Error> for Error {
+    fn from(err: bincode::Error) -> Self {
+        Error::Snapshot(err.to_string())
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Io(err.to_string())
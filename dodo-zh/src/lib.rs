@@ -1,27 +1,43 @@
 //! Dodo-zh is a crate which provide utilities method on pinyin and cedict file. It enables you to do the following operations
-//! - Load cedict file from a given path with the keys either being in Simplified or Traditional Chinese
+//! - Load cedict file from a given path, a reader, a string or a buffer of bytes with the keys either being in Simplified or Traditional Chinese
 //!
 //! Doing several operations on a given pinyin such as:
 //! - convert a pinyin to a zhuyin
 //! - convert a pinyin to a wade giles
+//! - convert a cantonese jyutping to yale
 //! - convert a pinyin which has number tones e.g: wo3 to a pinyin with tone markers wǒ
 //! - convert a pinyin with tones markers to numbers
 //! - convert a simplified <-> traditional text
 //! - detect chinese variant of a text
+//! - segment a chinese text into the words of the cedict dictionary
+//! - annotate a chinese text with the pinyin, zhuyin or wade giles of its words
+//! - choose the reading of polyphones such as 行 or 了 with the words of the cedict dictionary, the frequencies and user rules
+//!
+//! Character data such as stroke counts, radicals and definitions can be loaded from the Unihan database with the [`unihan`] module
+//! and the decomposition of the characters into their components with the [`decomposition`] module
 use crate::error::Error;
-use cedict::Dictionary;
+use annotation::{Annotation, Annotator};
+use cedict::{Dictionary, GlossLanguage, Overlay};
 use pinyin::accent::PinyinAccent;
 use pinyin::numbers::PinyinNumber;
+use polyphone::PolyphoneRules;
 use std::path::PathBuf;
 use variant::KeyVariant;
 use wade_giles::WadeGiles;
+use yale::Yale;
 use zhuyin::Zhuyin;
 
+pub mod annotation;
 pub mod cedict;
+pub mod decomposition;
 pub(crate) mod error;
 pub(crate) mod pinyin;
+pub mod polyphone;
+pub mod segmenter;
+pub mod unihan;
 pub mod variant;
 pub(crate) mod wade_giles;
+pub(crate) mod yale;
 pub(crate) mod zhuyin;
 
 // Constant
@@ -86,6 +102,38 @@ where
     Ok(res)
 }
 
+/// Convert a sequence of cantonese jyutping with tone numbers into the yale romanization with tone marks
+/// e.g: nei5 hou2 -> néih hóu
+///
+/// # Arguments
+///
+/// * `text` - S
+///
+/// # Examples
+///
+/// ```
+/// let yale = dodo_zh::convert_jyutping_to_yale("nei5 hou2").unwrap();
+/// assert_eq!(yale, "néih hóu");
+/// ```
+pub fn convert_jyutping_to_yale<S>(text: S) -> Result<String, Error>
+where
+    S: AsRef<str> + Clone,
+{
+    let splitted_text = text.as_ref().split_whitespace().collect::<Vec<_>>();
+
+    let res = splitted_text
+        .into_iter()
+        .map(|content| {
+            Yale(content)
+                .convert_jyutping_to_yale()
+                .ok_or_else(|| Error::Parse(format!("Unable to convert the jyutping {content}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .join(SEPARATOR);
+
+    Ok(res)
+}
+
 /// Convert a sequence of pinyin with number to a pinyin tone mark
 ///
 /// # Arguments
@@ -159,6 +207,63 @@ pub fn load_cedict_dictionary(p: PathBuf, key_variant: KeyVariant) -> Result<Dic
     Ok(dictionary)
 }
 
+/// Load a dictionary which use the cedict format with translations in the given language
+/// e.g: CFDICT (french) or HanDeDict (german)
+///
+/// # Arguments
+///
+/// * `p` - PathBuf
+/// * `key_variant` - KeyVariant
+/// * `language` - GlossLanguage
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::GlossLanguage;
+/// use dodo_zh::variant::KeyVariant;
+/// use std::path::PathBuf;
+///
+/// let dict = dodo_zh::load_cedict_dictionary_with_language(PathBuf::new(), KeyVariant::Traditional, GlossLanguage::French);
+/// ```
+pub fn load_cedict_dictionary_with_language(
+    p: PathBuf,
+    key_variant: KeyVariant,
+    language: GlossLanguage,
+) -> Result<Dictionary, Error> {
+    let dictionary = Dictionary::new(&p, key_variant)?.with_language(language);
+
+    Ok(dictionary)
+}
+
+/// Load Cedict Dictionary and stack the overlays on top of it in the given order
+///
+/// # Arguments
+///
+/// * `p` - PathBuf
+/// * `key_variant` - KeyVariant
+/// * `overlays` - &[Overlay]
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::{Overlay, OverlayPriority};
+/// use dodo_zh::variant::KeyVariant;
+/// use std::path::PathBuf;
+///
+/// let overlay = Overlay::from_reader("terms", "行 行 [hang2] /company/".as_bytes(), OverlayPriority::Replace).unwrap();
+/// let dict = dodo_zh::load_cedict_dictionary_with_overlays(PathBuf::new(), KeyVariant::Traditional, &[overlay]);
+/// ```
+pub fn load_cedict_dictionary_with_overlays(
+    p: PathBuf,
+    key_variant: KeyVariant,
+    overlays: &[Overlay],
+) -> Result<Dictionary, Error> {
+    let mut dictionary = Dictionary::new(&p, key_variant)?;
+    dictionary.apply_overlays(overlays);
+
+    Ok(dictionary)
+}
+
 /// Convert a chinese text to a desired variant (simplified <-> tradtional)
 ///
 /// # Arguments
@@ -182,10 +287,94 @@ pub fn convert_text_to_desired_variant<S: AsRef<str>>(
     input_variant: KeyVariant,
     target_variant: KeyVariant,
 ) -> Result<String, Error> {
-    variant::initialize_dictionaries(&p)?;
+    convert_text_to_desired_variant_with_overlays(p, &[], content, input_variant, target_variant)
+}
+
+/// Convert a chinese text to a desired variant (simplified <-> tradtional) by using the cedict dictionary
+/// with the overlays stacked on top of it.
+/// ⚠️ The dictionary is loaded by the first call with a given path and given overlays, and is then kept in memory.
+/// Use [`Dictionary::convert_text_to_variant`] to control the lifetime of the dictionary.
+///
+/// # Arguments
+///
+/// * `p` - PathBuf
+/// * `overlays` - &[Overlay]
+/// * `content` - S
+/// * `input_variant` - KeyVariant
+/// * `target_varaint` - KeyVariant
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::variant::KeyVariant;
+/// use std::path::PathBuf;
+///
+/// let converted = dodo_zh::convert_text_to_desired_variant_with_overlays(PathBuf::new(), &[], "大家好", KeyVariant::Traditional, KeyVariant::Simplified);
+/// ```
+pub fn convert_text_to_desired_variant_with_overlays<S: AsRef<str>>(
+    p: PathBuf,
+    overlays: &[Overlay],
+    content: S,
+    input_variant: KeyVariant,
+    target_variant: KeyVariant,
+) -> Result<String, Error> {
+    let dictionary = variant::initialize_dictionaries(&p, overlays)?;
 
-    variant::KeyVariant::convert_text_to_desired_variant(content, input_variant, target_variant)
-        .ok_or_else(|| Error::Parse("Unable to convert content to target key variant".to_string()))
+    Ok(dictionary.convert_text_to_variant(content, input_variant, target_variant))
+}
+
+/// Split a chinese text into words and annotate each word with the pinyin of its entry in the dictionary.
+/// The annotation can be rendered with tone numbers, tone marks, zhuyin or wade giles.
+///
+/// # Arguments
+///
+/// * `dictionary` - &'d Dictionary
+/// * `content` - &'t str
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::annotation::PinyinFormat;
+/// use dodo_zh::variant::KeyVariant;
+/// use std::path::PathBuf;
+///
+/// let dictionary = dodo_zh::load_cedict_dictionary(PathBuf::from("../static/cedict_sample_ts.u8"), KeyVariant::Traditional).unwrap();
+/// let annotation = dodo_zh::annotate(&dictionary, "我的朋友");
+/// let pinyin = annotation.to_pinyin(PinyinFormat::ToneNumber).unwrap();
+/// ```
+pub fn annotate<'d, 't>(dictionary: &'d Dictionary, content: &'t str) -> Annotation<'d, 't> {
+    Annotator::new(dictionary).annotate(content)
+}
+
+/// Split a chinese text into words and annotate each word with its pinyin. The readings of the polyphones
+/// e.g: 行, 长, 了 are chosen with the words of the dictionary and the rules override them.
+/// The characters which have several readings are listed by [`annotation::Annotation::ambiguities`]
+///
+/// # Arguments
+///
+/// * `dictionary` - &'d Dictionary
+/// * `rules` - &PolyphoneRules
+/// * `content` - &'t str
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::polyphone::PolyphoneRules;
+/// use dodo_zh::variant::KeyVariant;
+/// use std::path::PathBuf;
+///
+/// let dictionary = dodo_zh::load_cedict_dictionary(PathBuf::from("../static/cedict_sample_ts.u8"), KeyVariant::Traditional).unwrap();
+/// let rules = PolyphoneRules::from_reader("了 liao3".as_bytes()).unwrap();
+/// let annotation = dodo_zh::annotate_with_rules(&dictionary, &rules, "好了");
+/// ```
+pub fn annotate_with_rules<'d, 't>(
+    dictionary: &'d Dictionary,
+    rules: &PolyphoneRules,
+    content: &'t str,
+) -> Annotation<'d, 't> {
+    Annotator::new(dictionary)
+        .with_rules(rules)
+        .annotate(content)
 }
 
 /// Detect which variant of chinese is the text. If the given path for the cedict dictionary is passed
@@ -210,9 +399,8 @@ pub fn detect_which_variant<S: AsRef<str>>(
 ) -> Result<KeyVariant, Error> {
     match path {
         Some(p) => {
-            variant::initialize_dictionaries(&p)?;
-            variant::KeyVariant::which_variant(content)
-                .ok_or_else(|| Error::Parse("Unable to detect chinese variant".to_string()))
+            let dictionary = variant::initialize_dictionaries(&p, &[])?;
+            Ok(dictionary.which_variant(content))
         }
         None => Ok(variant::KeyVariant::detect_variant_with_unicode(content)),
     }
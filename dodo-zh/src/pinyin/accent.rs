@@ -1,7 +1,8 @@
 // Constant
 const MEDIAL_VOWEL: [char; 2] = ['i', 'u'];
 const VOWEL: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
-const TONES: [&str; 4] = ["\u{0304}", "\u{0301}", "\u{030c}", "\u{0300}"];
+// Combining tone marks of the tones 1 to 4. These are also used to read the tone of a pinyin
+pub(crate) const TONES: [char; 4] = ['\u{0304}', '\u{0301}', '\u{030c}', '\u{0300}'];
 const TONES_U: [&str; 4] = ["ū", "ú", "ǔ", "ù"];
 
 /// Create & manipulate a pinyin to convert into an accent one.
@@ -15,12 +16,12 @@ where
 {
     /// Replace the tone numberes i.e: xi1 with the tone marks
     /// - Tones are only placed in a vowel
-    ///     - There are some rules. Please refer to readme for the link of the rules but in short:
-    ///         - 1 vowel only -> add the marker tone to the vowel
-    ///         - More than 2 vowels
-    ///             -> If first vowel is a medial vowel then the next letter (vowel) should have the tone marker
-    ///             -> Otherwise, the first vowel has the tone marker
-    ///         - Vowel that are marked for the 'u' character are usually prceded by a ':'. These are replaced by a special 'u' character
+    ///   - There are some rules. Please refer to readme for the link of the rules but in short:
+    ///     - 1 vowel only -> add the marker tone to the vowel
+    ///     - More than 2 vowels
+    ///       -> If first vowel is a medial vowel then the next letter (vowel) should have the tone marker
+    ///       -> Otherwise, the first vowel has the tone marker
+    ///     - Vowel that are marked for the 'u' character are usually prceded by a ':'. These are replaced by a special 'u' character
     ///
     /// # Arguments
     ///
@@ -39,7 +40,10 @@ where
         let (tone_index, tone_mark) = match tone_marker {
             Some(t) => {
                 let index = t.to_digit(10).unwrap_or_default().overflowing_sub(1).0 as usize;
-                (index, TONES.get(index).unwrap_or(&""))
+                (
+                    index,
+                    TONES.get(index).map(char::to_string).unwrap_or_default(),
+                )
             }
             None => return Some(self.0.as_ref().to_string()),
         };
@@ -86,10 +90,10 @@ where
             // If the first vowel is a MEDIAL Vowel, then the next vowel which is the next letter
             // is the one who has the marker tone
             if MEDIAL_VOWEL.contains(c) {
-                if let Some((n_idx, next)) = iterator.next() {
-                    if VOWEL.contains(next) {
-                        return Some(n_idx);
-                    }
+                if let Some((n_idx, next)) = iterator.next()
+                    && VOWEL.contains(next)
+                {
+                    return Some(n_idx);
                 }
 
                 // Otherwise this mean that we're at the end word meaning that the
@@ -1,5 +1,11 @@
+use super::accent::TONES;
 use unicode_normalization::UnicodeNormalization;
 
+// Constant
+const DIAERESIS: char = '\u{0308}';
+const CEDICT_DIAERESIS: char = ':';
+const NEUTRAL_TONE: char = '5';
+
 /// PinyinNumber is handler which allows to convert a pinyin with accent to a pinyin with a number
 pub struct PinyinNumber<S>(pub S)
 where
@@ -30,6 +36,43 @@ where
 
         pinyin.iter().collect()
     }
+
+    /// Split the pinyin into its letters and its tones. The pinyin can be written with tone numbers or tone marks
+    /// and may have several syllables e.g: `ni3 hao3`, `nǐhǎo`. The letters are lowercased without the spaces and
+    /// the apostrophes, and the ü is written as a `v` whether it's written `ü` or `u:` like in the cedict file.
+    pub(crate) fn into_tones(self) -> PinyinTones {
+        let mut tones = PinyinTones::default();
+
+        for c in self.0.as_ref().to_lowercase().nfd() {
+            let tone = get_char(c)
+                .or(('1'..=NEUTRAL_TONE).contains(&c).then_some(c))
+                .and_then(|t| t.to_digit(10));
+
+            match (c, tone) {
+                (_, Some(tone)) if !tones.letters.is_empty() => {
+                    tones.tones.push((tones.letters.len() - 1, tone))
+                }
+                (DIAERESIS | CEDICT_DIAERESIS, None) => {
+                    if let Some(last) = tones.letters.last_mut().filter(|l| **l == 'u') {
+                        *last = 'v';
+                    }
+                }
+                (c, None) if c.is_alphabetic() => tones.letters.push(c),
+                // Spaces, apostrophes and tones without any letter before them are ignored
+                _ => {}
+            }
+        }
+
+        tones
+    }
+}
+
+/// PinyinTones is a pinyin without tones with the list of tones found in the pinyin. Each tone is
+/// associated to the position of the letter that the tone follow or is placed on.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PinyinTones {
+    pub letters: Vec<char>,
+    pub tones: Vec<(usize, u32)>,
 }
 
 /// Transform the accent into their number representation
@@ -37,14 +80,11 @@ where
 /// # Arguments
 ///
 /// * `ch` - char
-fn get_char(ch: char) -> Option<char> {
-    match ch {
-        '\u{0304}' => Some('1'),
-        '\u{0301}' => Some('2'),
-        '\u{030c}' => Some('3'),
-        '\u{0300}' => Some('4'),
-        _ => None,
-    }
+pub(crate) fn get_char(ch: char) -> Option<char> {
+    TONES
+        .iter()
+        .position(|tone| *tone == ch)
+        .and_then(|idx| char::from_digit(idx as u32 + 1, 10))
 }
 
 #[cfg(test)]
@@ -64,4 +104,15 @@ mod tests {
 
         assert_eq!(p, "huan1");
     }
+
+    #[test]
+    fn expect_to_split_tones_of_several_syllables() {
+        let tones = PinyinNumber("Nǚ'ér").into_tones();
+        assert_eq!(tones.letters.iter().collect::<String>(), "nver");
+        assert_eq!(tones.tones, vec![(1, 3), (2, 2)]);
+
+        let tones = PinyinNumber("nu:3 er2").into_tones();
+        assert_eq!(tones.letters.iter().collect::<String>(), "nver");
+        assert_eq!(tones.tones, vec![(1, 3), (3, 2)]);
+    }
 }
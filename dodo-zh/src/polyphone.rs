@@ -0,0 +1,218 @@
+use crate::error::Error;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::ops::Range;
+use std::path::PathBuf;
+
+// Constant
+const COMMENT: char = '#';
+
+/// Resolution is how the reading of a polyphone has been chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The character is part of a headword of the dictionary which has a single reading e.g: 银行 -> hang2
+    Word,
+    /// The most common reading is used as the headword has several readings e.g: 了 -> le5
+    Frequency,
+    /// The reading is set by a rule of the user
+    Rule,
+}
+
+/// Ambiguity is a character of the text which has several readings in the dictionary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Ambiguity {
+    pub character: char,
+    /// Byte span of the character in the text
+    pub span: Range<usize>,
+    /// Reading which has been chosen
+    pub reading: String,
+    /// Every readings of the character from the most common to the least common one
+    pub candidates: Vec<String>,
+    pub resolution: Resolution,
+}
+
+/// UnappliedRule is a rule whose phrase appear in the text but whose readings could not be set e.g: the phrase
+/// cover an erhua headword like 玩儿 [wanr2] which has less syllables than characters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnappliedRule {
+    pub phrase: String,
+    /// Byte span of the phrase in the text
+    pub span: Range<usize>,
+}
+
+/// PolyphoneRule force the reading of the characters of a phrase e.g: 还是 -> hai2 shi4
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolyphoneRule {
+    pub phrase: String,
+    pub pinyin_tone_number: Vec<String>,
+}
+
+/// PolyphoneRules is a list of rules which override the readings found with the dictionary. A rule file has
+/// one phrase per line followed by the pinyin of each of its characters. Lines starting with `#` are comments.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::polyphone::PolyphoneRules;
+///
+/// let rules = PolyphoneRules::from_reader("# context rules\n还是 hai2 shi4\n了 le5".as_bytes()).unwrap();
+///
+/// assert_eq!(rules.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PolyphoneRules {
+    rules: Vec<PolyphoneRule>,
+}
+
+impl PolyphoneRules {
+    /// Load the rules from the file path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - &PathBuf
+    pub fn new(path: &PathBuf) -> Result<PolyphoneRules, Error> {
+        PolyphoneRules::from_reader(File::open(path)?)
+    }
+
+    /// Load the rules from any reader
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - R
+    pub fn from_reader<R: Read>(reader: R) -> Result<PolyphoneRules, Error> {
+        let mut rules = PolyphoneRules::default();
+
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let content = line.trim();
+            if content.is_empty() || content.starts_with(COMMENT) {
+                continue;
+            }
+
+            let mut columns = content.split_whitespace();
+            let phrase = columns.next().unwrap_or_default();
+            let pinyin = columns.collect::<Vec<_>>();
+
+            let rule = new_rule(phrase, &pinyin).map_err(|reason| Error::ParseLine {
+                line: idx + 1,
+                column: line
+                    .find(phrase)
+                    .map_or(0, |offset| line[..offset].chars().count())
+                    + 1,
+                reason,
+            })?;
+            rules.rules.push(rule);
+        }
+
+        Ok(rules)
+    }
+
+    /// Add a rule. The phrase must have one syllable per character
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `phrase` - &str
+    /// * `pinyin` - &[S]
+    pub fn add<S: AsRef<str>>(&mut self, phrase: &str, pinyin: &[S]) -> Result<(), Error> {
+        let rule = new_rule(phrase, pinyin).map_err(Error::Parse)?;
+        self.rules.push(rule);
+
+        Ok(())
+    }
+
+    /// Get the rules whose phrase appear in the text with their byte span. Longer phrases win over the shorter
+    /// ones and a phrase added later win over a phrase of the same length. The matches do not overlap
+    /// and are in the order of the text
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &str
+    pub(crate) fn matches(&self, text: &str) -> Vec<(Range<usize>, &PolyphoneRule)> {
+        let mut rules = self.rules.iter().enumerate().collect::<Vec<_>>();
+        rules.sort_by_key(|(idx, rule)| Reverse((rule.phrase.chars().count(), *idx)));
+
+        let mut matches: Vec<(Range<usize>, &PolyphoneRule)> = Vec::new();
+        for (_, rule) in rules {
+            for (start, _) in text.match_indices(&rule.phrase) {
+                let span = start..start + rule.phrase.len();
+                if matches
+                    .iter()
+                    .all(|(other, _)| span.end <= other.start || other.end <= span.start)
+                {
+                    matches.push((span, rule));
+                }
+            }
+        }
+
+        matches.sort_by_key(|(span, _)| span.start);
+
+        matches
+    }
+
+    /// Return the number of rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check whether there are no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Create a rule. The phrase must have one syllable per character
+///
+/// # Arguments
+///
+/// * `phrase` - &str
+/// * `pinyin` - &[S]
+fn new_rule<S: AsRef<str>>(phrase: &str, pinyin: &[S]) -> Result<PolyphoneRule, String> {
+    let characters = phrase.chars().count();
+    if characters == 0 || characters != pinyin.len() {
+        return Err(format!(
+            "the phrase {phrase} has {characters} characters but {} syllables",
+            pinyin.len()
+        ));
+    }
+
+    Ok(PolyphoneRule {
+        phrase: phrase.to_string(),
+        pinyin_tone_number: pinyin.iter().map(|p| p.as_ref().to_string()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_to_reject_rule_with_wrong_syllable_count() {
+        let res = PolyphoneRules::from_reader("还是 hai2 shi4\n  长大 zhang3".as_bytes());
+
+        assert!(matches!(
+            res,
+            Err(Error::ParseLine {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn expect_to_prefer_longest_rules() {
+        let rules = PolyphoneRules::from_reader("还 huan2\n还是 hai2 shi4".as_bytes()).unwrap();
+
+        let matches = rules
+            .matches("还是还书")
+            .into_iter()
+            .map(|(span, rule)| (span, rule.phrase.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(matches, vec![(0..6, "还是"), (6..9, "还")]);
+    }
+}
@@ -0,0 +1,478 @@
+use crate::cedict::{Dictionary, Item};
+use crate::variant::KeyVariant;
+use serde::Serialize;
+use std::ops::Range;
+
+// Constant
+const PER_MILLION: f64 = 1_000_000.0;
+// Frequency given to the headwords of the dictionary which do not have a frequency
+const DEFAULT_PER_MILLION: f64 = 1.0;
+// Frequency given to the chinese characters which are not in the dictionary
+const UNKNOWN_PER_MILLION: f64 = 0.01;
+
+/// SegmentMode is the algorithm used to split the chinese runs of a text into words
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentMode {
+    /// Take the longest headword of the dictionary from left to right
+    MaximumMatching,
+    /// Build the graph of every headwords of the text and take the most probable path based on the frequency
+    /// of the headwords. Headwords without a frequency are considered as rare words. Hence without a frequency
+    /// list the path with the least words is taken.
+    #[default]
+    Dag,
+}
+
+/// TokenKind is the kind of a token of a segmented text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    /// A headword of the dictionary
+    Word,
+    /// A chinese character which is not in the dictionary
+    Unknown,
+    /// A run of latin letters or digits e.g: iPhone15
+    Alphanumeric,
+    /// A run of letters of an other script e.g: kana, hangul or cyrillic
+    Other,
+    /// A run of whitespaces
+    Whitespace,
+    /// A run of punctuations or symbols e.g: ，or ...
+    Punctuation,
+}
+
+/// Token is a word of a segmented text with its byte span in the text and the entries of the headword
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Token<'d, 't> {
+    pub text: &'t str,
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+    pub items: Vec<&'d Item>,
+}
+
+/// Segmenter split a text into words by using the headwords of a dictionary. The runs which are not
+/// written in chinese are kept as single tokens e.g: latin words, numbers and punctuations, unless
+/// they are part of a headword which mix chinese characters and latin letters e.g: 卡拉OK.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::cedict::Dictionary;
+/// use dodo_zh::segmenter::{Segmenter, SegmentMode};
+/// use dodo_zh::variant::KeyVariant;
+///
+/// let dictionary = Dictionary::from_str("朋友 朋友 [peng2 you5] /friend/\n我 我 [wo3] /I/", KeyVariant::Simplified).unwrap();
+/// let tokens = Segmenter::new(&dictionary)
+///     .with_mode(SegmentMode::MaximumMatching)
+///     .segment("我朋友 Tom");
+///
+/// let words = tokens.iter().map(|token| token.text).collect::<Vec<_>>();
+/// assert_eq!(words, vec!["我", "朋友", " ", "Tom"]);
+/// assert_eq!(tokens[1].span, 3..9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Segmenter<'d> {
+    dictionary: &'d Dictionary,
+    mode: SegmentMode,
+    variant: Option<KeyVariant>,
+}
+
+impl<'d> Segmenter<'d> {
+    /// Create a new segmenter which match the simplified and the traditional headwords of the dictionary
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary` - &'d Dictionary
+    pub fn new(dictionary: &'d Dictionary) -> Self {
+        Self {
+            dictionary,
+            mode: SegmentMode::default(),
+            variant: None,
+        }
+    }
+
+    /// Set the algorithm used to split the chinese runs
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `mode` - SegmentMode
+    pub fn with_mode(mut self, mode: SegmentMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Only match the headwords written in the given variant
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `variant` - KeyVariant
+    pub fn with_variant(mut self, variant: KeyVariant) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+
+    /// Split the text into tokens. The tokens cover the whole text in order
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &'t str
+    pub fn segment<'t>(&self, text: &'t str) -> Vec<Token<'d, 't>> {
+        let mut tokens = Vec::new();
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            let class = CharClass::from(c);
+
+            let mut end = start + c.len_utf8();
+            while let Some((offset, next)) = chars.peek()
+                && class.same_run(CharClass::from(*next))
+            {
+                end = offset + next.len_utf8();
+                chars.next();
+            }
+
+            let kind = match class {
+                CharClass::Han | CharClass::Alphanumeric => {
+                    self.segment_run(text, start..end, &mut tokens);
+                    continue;
+                }
+                CharClass::Other => TokenKind::Other,
+                CharClass::Whitespace => TokenKind::Whitespace,
+                CharClass::Punctuation => TokenKind::Punctuation,
+            };
+
+            tokens.push(Token {
+                text: &text[start..end],
+                span: start..end,
+                kind,
+                items: Vec::new(),
+            });
+        }
+
+        tokens
+    }
+
+    /// Split a run of chinese characters and latin letters or digits into words. A headword may mix chinese
+    /// characters with latin letters e.g: 卡拉OK, T恤. Hence the headwords are matched across the whole run
+    /// but a latin word is never split and a headword must contain at least a chinese character.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &'t str
+    /// * `run` - Range<usize>
+    /// * `tokens` - &mut Vec<Token<'d, 't>>
+    fn segment_run<'t>(&self, text: &'t str, run: Range<usize>, tokens: &mut Vec<Token<'d, 't>>) {
+        let classes = text[run.clone()]
+            .chars()
+            .map(CharClass::from)
+            .collect::<Vec<_>>();
+
+        // Byte offset of each character of the run with the end of the run
+        let offsets = text[run.clone()]
+            .char_indices()
+            .map(|(offset, _)| run.start + offset)
+            .chain([run.end])
+            .collect::<Vec<_>>();
+
+        // A word can only start or end next to a chinese character or at the edges of the run
+        let is_boundary = |idx: usize| {
+            idx == 0
+                || idx == classes.len()
+                || classes[idx - 1] == CharClass::Han
+                || classes[idx] == CharClass::Han
+        };
+
+        // The words starting at each character. A word is the index of the character after the word with
+        // the entries of the word. Shorter words come first. A single chinese character or a whole latin
+        // word is always a word
+        let graph = (0..classes.len())
+            .map(|idx| {
+                let mut words = match is_boundary(idx) {
+                    true => self
+                        .dictionary
+                        .prefix_matches(&text[offsets[idx]..run.end], self.variant.as_ref())
+                        .into_iter()
+                        .map(|(end, items)| {
+                            let next =
+                                offsets.partition_point(|offset| *offset < offsets[idx] + end);
+                            (next, items)
+                        })
+                        .filter(|(next, _)| {
+                            is_boundary(*next) && classes[idx..*next].contains(&CharClass::Han)
+                        })
+                        .collect::<Vec<_>>(),
+                    false => Vec::new(),
+                };
+
+                let next = match classes[idx] {
+                    CharClass::Han => idx + 1,
+                    _ => (idx + 1..=classes.len())
+                        .find(|next| is_boundary(*next))
+                        .unwrap_or(classes.len()),
+                };
+
+                if words.first().is_none_or(|(idx, _)| *idx != next) {
+                    words.insert(0, (next, Vec::new()));
+                }
+
+                words
+            })
+            .collect::<Vec<_>>();
+
+        let mut idx = 0;
+        let route = match self.mode {
+            SegmentMode::MaximumMatching => None,
+            SegmentMode::Dag => Some(best_route(&graph)),
+        };
+
+        while idx < graph.len() {
+            let (next, items) = match &route {
+                Some(route) => graph[idx].iter().find(|(next, _)| *next == route[idx]),
+                None => graph[idx].last(),
+            }
+            .cloned()
+            .unwrap_or((idx + 1, Vec::new()));
+
+            let span = offsets[idx]..offsets[next];
+            tokens.push(Token {
+                text: &text[span.clone()],
+                span,
+                kind: match (items.is_empty(), classes[idx]) {
+                    (false, _) => TokenKind::Word,
+                    (true, CharClass::Han) => TokenKind::Unknown,
+                    (true, _) => TokenKind::Alphanumeric,
+                },
+                items,
+            });
+
+            idx = next;
+        }
+    }
+}
+
+impl Dictionary {
+    /// Split a text into words with the headwords of the dictionary by using the default segment mode
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `text` - &'t str
+    pub fn segment<'t>(&self, text: &'t str) -> Vec<Token<'_, 't>> {
+        Segmenter::new(self).segment(text)
+    }
+}
+
+/// Get the most probable path of the graph of words. The probability of a path is the product of the frequency
+/// of its words. The route is the index of the character following the best word starting at each character
+///
+/// # Arguments
+///
+/// * `graph` - &[Vec<(usize, Vec<&Item>)>]
+fn best_route(graph: &[Vec<(usize, Vec<&Item>)>]) -> Vec<usize> {
+    let mut scores = vec![0.0; graph.len() + 1];
+    let mut route = vec![0; graph.len()];
+
+    for idx in (0..graph.len()).rev() {
+        let mut best = f64::NEG_INFINITY;
+        for (next, items) in &graph[idx] {
+            let score = word_weight(items) + scores[*next];
+            // Longer words come last hence they win on equal scores
+            if score >= best {
+                best = score;
+                route[idx] = *next;
+            }
+        }
+
+        scores[idx] = best;
+    }
+
+    route
+}
+
+/// Get the log probability of a word from the most frequent of its entries
+///
+/// # Arguments
+///
+/// * `items` - &[&Item]
+fn word_weight(items: &[&Item]) -> f64 {
+    let per_million = match items.is_empty() {
+        true => UNKNOWN_PER_MILLION,
+        false => items
+            .iter()
+            .filter_map(|item| item.frequency.map(|frequency| frequency.per_million))
+            .fold(DEFAULT_PER_MILLION, f64::max),
+    };
+
+    (per_million / PER_MILLION).ln()
+}
+
+/// CharClass is the class of a character which is used to group the characters into runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Han,
+    Alphanumeric,
+    Other,
+    Whitespace,
+    Punctuation,
+}
+
+impl From<char> for CharClass {
+    fn from(c: char) -> Self {
+        match c {
+            '\u{3007}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+            | '\u{30000}'..='\u{323AF}' => CharClass::Han,
+            // Latin letters with or without diacritics, digits and their fullwidth forms e.g: Ｔ恤
+            '0'..='9'
+            | 'A'..='Z'
+            | 'a'..='z'
+            | '\u{FF10}'..='\u{FF19}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}' => CharClass::Alphanumeric,
+            '\u{00C0}'..='\u{024F}' if c.is_alphabetic() => CharClass::Alphanumeric,
+            c if c.is_whitespace() => CharClass::Whitespace,
+            c if c.is_alphanumeric() => CharClass::Other,
+            _ => CharClass::Punctuation,
+        }
+    }
+}
+
+impl CharClass {
+    /// Chinese characters and latin letters or digits are in the same run since a headword may mix them
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - Self
+    /// * `other` - CharClass
+    fn same_run(self, other: CharClass) -> bool {
+        let is_word = |class| matches!(class, CharClass::Han | CharClass::Alphanumeric);
+
+        self == other || is_word(self) && is_word(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cedict::FrequencyList;
+
+    fn load_dictionary() -> Dictionary {
+        Dictionary::from_str(
+            "研究 研究 [yan2 jiu1] /research/\n研究生 研究生 [yan2 jiu1 sheng1] /graduate student/\n生命 生命 [sheng1 ming4] /life/\n起源 起源 [qi3 yuan2] /origin/\n的 的 [de5] /of/\n命 命 [ming4] /life; fate/\n生 生 [sheng1] /to be born/",
+            KeyVariant::Simplified,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn expect_to_segment_with_maximum_matching() {
+        let dictionary = load_dictionary();
+        let tokens = Segmenter::new(&dictionary)
+            .with_mode(SegmentMode::MaximumMatching)
+            .segment("研究生命的起源");
+
+        let words = tokens.iter().map(|token| token.text).collect::<Vec<_>>();
+        assert_eq!(words, vec!["研究生", "命", "的", "起源"]);
+    }
+
+    #[test]
+    fn expect_to_segment_with_frequencies() {
+        let mut dictionary = load_dictionary();
+        let list = FrequencyList::from_reader(
+            "的\t5000\n研究\t300\n生命\t200\n起源\t50\n研究生\t20\n生\t10\n命\t10".as_bytes(),
+        )
+        .unwrap();
+        dictionary.apply_frequencies(&list);
+
+        let text = "我们研究生命的起源, 2024年！";
+        let tokens = dictionary.segment(text);
+
+        let words = tokens
+            .iter()
+            .map(|token| (token.text, token.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            vec![
+                ("我", TokenKind::Unknown),
+                ("们", TokenKind::Unknown),
+                ("研究", TokenKind::Word),
+                ("生命", TokenKind::Word),
+                ("的", TokenKind::Word),
+                ("起源", TokenKind::Word),
+                (",", TokenKind::Punctuation),
+                (" ", TokenKind::Whitespace),
+                ("2024", TokenKind::Alphanumeric),
+                ("年", TokenKind::Unknown),
+                ("！", TokenKind::Punctuation),
+            ]
+        );
+        assert!(
+            tokens
+                .iter()
+                .all(|token| &text[token.span.clone()] == token.text)
+        );
+        assert_eq!(tokens[2].items[0].translations, vec!["research"]);
+    }
+
+    #[test]
+    fn expect_to_segment_mixed_headwords() {
+        let dictionary = Dictionary::from_str(
+            "卡拉OK 卡拉OK [ka3 la1 O K] /karaoke/\nT恤 T恤 [T xu4] /T-shirt/\n我 我 [wo3] /I/\n穿 穿 [chuan1] /to wear/",
+            KeyVariant::Simplified,
+        )
+        .unwrap();
+
+        for mode in [SegmentMode::MaximumMatching, SegmentMode::Dag] {
+            let tokens = Segmenter::new(&dictionary)
+                .with_mode(mode)
+                .segment("我穿T恤去卡拉OK, iPhone15T");
+
+            let words = tokens
+                .iter()
+                .map(|token| (token.text, token.kind))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                words,
+                vec![
+                    ("我", TokenKind::Word),
+                    ("穿", TokenKind::Word),
+                    ("T恤", TokenKind::Word),
+                    ("去", TokenKind::Unknown),
+                    ("卡拉OK", TokenKind::Word),
+                    (",", TokenKind::Punctuation),
+                    (" ", TokenKind::Whitespace),
+                    ("iPhone15T", TokenKind::Alphanumeric),
+                ],
+                "{mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn expect_to_keep_other_scripts_apart() {
+        let dictionary = load_dictionary();
+        let tokens = dictionary.segment("研究はとても面白いです");
+
+        let words = tokens
+            .iter()
+            .map(|token| (token.text, token.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            vec![
+                ("研究", TokenKind::Word),
+                ("はとても", TokenKind::Other),
+                ("面", TokenKind::Unknown),
+                ("白", TokenKind::Unknown),
+                ("いです", TokenKind::Other),
+            ]
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use crate::cedict::{Dictionary, Item};
+use crate::error::Error;
+use crate::pinyin::numbers::PinyinNumber;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+// Constant
+const UNIHAN_FILES: [&str; 3] = [
+    "Unihan_Readings.txt",
+    "Unihan_IRGSources.txt",
+    "Unihan_RadicalStrokeCounts.txt",
+];
+const CODEPOINT_PREFIX: &str = "U+";
+const DEFINITION_SEPARATOR: &str = ";";
+/// Layer of the items which are built from the Unihan database
+pub const UNIHAN_LAYER: &str = "unihan";
+
+/// CharacterData is the data of a codepoint of the Unihan database. Readings are written with tone marks
+/// e.g: hǎo as in the Unihan files
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct CharacterData {
+    /// Most customary readings of the kMandarin field
+    pub mandarin: Vec<String>,
+    /// Readings of the Hanyu Da Zidian of the kHanyuPinyin field without the location in the dictionary
+    pub hanyu_pinyin: Vec<String>,
+    /// Total number of strokes of the kTotalStrokes field. A second value may be given for the traditional form
+    pub total_strokes: Vec<u32>,
+    /// Radical and additional strokes of the kRSUnicode field
+    pub radical_strokes: Vec<RadicalStroke>,
+    /// English definition of the kDefinition field
+    pub definition: Option<String>,
+}
+
+/// RadicalStroke is the kangxi radical of a character with the number of strokes besides the radical
+/// e.g: `38.3` for 好
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RadicalStroke {
+    pub radical: u8,
+    /// Whether the simplified form of the radical is used e.g: `187'.0` for 马
+    pub simplified_radical: bool,
+    pub additional_strokes: i32,
+}
+
+/// Unihan is the character data of the Unicode Han Database. It can be used as a fallback for the characters
+/// which are missing from the cedict dictionary.
+///
+/// # Examples
+///
+/// ```
+/// use dodo_zh::unihan::Unihan;
+///
+/// let mut unihan = Unihan::default();
+/// unihan.load("U+597D\tkMandarin\thǎo\nU+597D\tkTotalStrokes\t6".as_bytes()).unwrap();
+///
+/// let data = unihan.get('好').unwrap();
+/// assert_eq!(data.total_strokes, vec![6]);
+/// assert_eq!(unihan.readings('好'), vec!["hao3"]);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Unihan {
+    characters: HashMap<char, CharacterData>,
+}
+
+impl Unihan {
+    /// Load the given Unihan files e.g: Unihan_Readings.txt. The fields of a codepoint which are spread
+    /// across the files are merged
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - &[PathBuf]
+    pub fn new(paths: &[PathBuf]) -> Result<Unihan, Error> {
+        let mut unihan = Unihan::default();
+        for path in paths {
+            unihan.load(File::open(path)?)?;
+        }
+
+        Ok(unihan)
+    }
+
+    /// Load the Unihan_Readings.txt, Unihan_IRGSources.txt and Unihan_RadicalStrokeCounts.txt files of
+    /// the unzipped Unihan.zip archive. Missing files are skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - &Path
+    pub fn from_dir(dir: &Path) -> Result<Unihan, Error> {
+        let paths = UNIHAN_FILES
+            .iter()
+            .map(|file| dir.join(file))
+            .filter(|path| path.exists())
+            .collect::<Vec<_>>();
+
+        if paths.is_empty() {
+            return Err(Error::Unihan(format!(
+                "no unihan files found in {}",
+                dir.display()
+            )));
+        }
+
+        Unihan::new(&paths)
+    }
+
+    /// Load the lines of a Unihan file with the format `U+597D<TAB>kMandarin<TAB>hǎo`. Fields which are not
+    /// used are skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `reader` - R
+    pub fn load<R: Read>(&mut self, reader: R) -> Result<(), Error> {
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut columns = line.splitn(3, '\t');
+            let (Some(codepoint), Some(field), Some(value)) =
+                (columns.next(), columns.next(), columns.next())
+            else {
+                return Err(Error::Unihan(format!(
+                    "line {} does not have a codepoint, a field and a value",
+                    idx + 1
+                )));
+            };
+
+            let character = parse_codepoint(codepoint).ok_or_else(|| {
+                Error::Unihan(format!(
+                    "line {} has an invalid codepoint {codepoint}",
+                    idx + 1
+                ))
+            })?;
+
+            let data = self.characters.entry(character).or_default();
+            match field {
+                "kMandarin" => data.mandarin = split_values(value),
+                "kHanyuPinyin" => {
+                    data.hanyu_pinyin = Vec::new();
+                    // Each value is the location in the dictionary followed by the readings e.g: 10993.020:hǎo,hào
+                    for reading in value
+                        .split_whitespace()
+                        .filter_map(|location| location.split_once(':'))
+                        .flat_map(|(_, readings)| readings.split(','))
+                    {
+                        if !data.hanyu_pinyin.iter().any(|r| r == reading) {
+                            data.hanyu_pinyin.push(reading.to_string());
+                        }
+                    }
+                }
+                "kTotalStrokes" => {
+                    data.total_strokes = value
+                        .split_whitespace()
+                        .filter_map(|strokes| strokes.parse().ok())
+                        .collect()
+                }
+                "kRSUnicode" => {
+                    data.radical_strokes = value
+                        .split_whitespace()
+                        .filter_map(parse_radical_stroke)
+                        .collect()
+                }
+                "kDefinition" => data.definition = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the data of a character
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - char
+    pub fn get(&self, character: char) -> Option<&CharacterData> {
+        self.characters.get(&character)
+    }
+
+    /// Get the readings of a character with tone numbers e.g: hao3. The kMandarin readings are used first
+    /// and the kHanyuPinyin readings otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - char
+    pub fn readings(&self, character: char) -> Vec<String> {
+        let Some(data) = self.get(character) else {
+            return Vec::new();
+        };
+
+        match data.mandarin.is_empty() {
+            true => &data.hanyu_pinyin,
+            false => &data.mandarin,
+        }
+        .iter()
+        .map(|reading| into_tone_number(reading))
+        .collect()
+    }
+
+    /// Build a cedict item from the data of a character. The item use the first reading and the definition
+    /// of the character. The item is `None` when the character does not have a reading
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `character` - char
+    pub fn to_item(&self, character: char) -> Option<Item> {
+        let reading = self.readings(character).into_iter().next()?;
+        let definition = self
+            .get(character)?
+            .definition
+            .as_deref()
+            .unwrap_or_default();
+
+        Some(Item {
+            traditional_character: character.to_string(),
+            simplified_character: character.to_string(),
+            pinyin_tone_number: vec![reading],
+            translations: definition
+                .split(DEFINITION_SEPARATOR)
+                .map(str::trim)
+                .filter(|translation| !translation.is_empty())
+                .map(str::to_string)
+                .collect(),
+            layer: Some(UNIHAN_LAYER.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Return the number of characters
+    pub fn len(&self) -> usize {
+        self.characters.len()
+    }
+
+    /// Check whether there is no characters
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+}
+
+impl Dictionary {
+    /// Lookup the headword in the dictionary whatever its script is. A character which is missing from the
+    /// dictionary is built from the Unihan database instead
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    /// * `headword` - S
+    /// * `unihan` - &Unihan
+    pub fn lookup_with_fallback<S: AsRef<str>>(
+        &self,
+        headword: S,
+        unihan: &Unihan,
+    ) -> Vec<Cow<'_, Item>> {
+        let items = self.lookup(headword.as_ref());
+        if !items.is_empty() {
+            return items.into_iter().map(Cow::Borrowed).collect();
+        }
+
+        let mut chars = headword.as_ref().chars();
+        match (chars.next(), chars.next()) {
+            (Some(character), None) => unihan
+                .to_item(character)
+                .map(Cow::Owned)
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Parse a codepoint with the format `U+597D`
+///
+/// # Arguments
+///
+/// * `codepoint` - &str
+fn parse_codepoint(codepoint: &str) -> Option<char> {
+    let hex = codepoint.strip_prefix(CODEPOINT_PREFIX)?;
+
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// Parse a radical stroke count with the format `38.3`. The apostrophes after the radical mark a simplified radical
+///
+/// # Arguments
+///
+/// * `value` - &str
+fn parse_radical_stroke(value: &str) -> Option<RadicalStroke> {
+    let (radical, strokes) = value.split_once('.')?;
+    let trimmed = radical.trim_end_matches('\'');
+
+    Some(RadicalStroke {
+        radical: trimmed.parse().ok()?,
+        simplified_radical: trimmed.len() != radical.len(),
+        additional_strokes: strokes.parse().ok()?,
+    })
+}
+
+/// Split the values of a field which are separated by spaces
+///
+/// # Arguments
+///
+/// * `value` - &str
+fn split_values(value: &str) -> Vec<String> {
+    value.split_whitespace().map(str::to_string).collect()
+}
+
+/// Convert a reading with tone marks into a reading with tone numbers in the cedict format e.g: nǚ -> nu:3
+///
+/// # Arguments
+///
+/// * `reading` - &str
+fn into_tone_number(reading: &str) -> String {
+    let pinyin = PinyinNumber(reading).into_number();
+    // A reading without tone marks has the neutral tone
+    let pinyin = match pinyin.strip_suffix(char::default()) {
+        Some(pinyin) => format!("{pinyin}5"),
+        None => pinyin,
+    };
+
+    pinyin.replace("u\u{0308}", "u:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::KeyVariant;
+
+    #[test]
+    fn expect_to_load_unihan_files() {
+        let unihan = Unihan::from_dir(Path::new("../static/unihan")).unwrap();
+        assert_eq!(unihan.len(), 5);
+
+        let hao = unihan.get('好').unwrap();
+        assert_eq!(hao.mandarin, vec!["hǎo"]);
+        assert_eq!(hao.hanyu_pinyin, vec!["hǎo", "hào"]);
+        assert_eq!(hao.total_strokes, vec![6]);
+        assert_eq!(
+            hao.radical_strokes,
+            vec![RadicalStroke {
+                radical: 38,
+                simplified_radical: false,
+                additional_strokes: 3
+            }]
+        );
+        assert_eq!(
+            hao.definition.as_deref(),
+            Some("good, excellent, fine; well")
+        );
+
+        assert!(unihan.get('马').unwrap().radical_strokes[0].simplified_radical);
+        assert_eq!(unihan.readings('女'), vec!["nu:3"]);
+        assert_eq!(unihan.readings('𠀀'), vec!["he1", "ke4"]);
+        assert_eq!(unihan.get('𠀀').unwrap().total_strokes, vec![3, 4]);
+    }
+
+    #[test]
+    fn expect_to_fallback_on_unihan() {
+        let unihan = Unihan::from_dir(Path::new("../static/unihan")).unwrap();
+        let dictionary = Dictionary::new(
+            &PathBuf::from("../static/cedict_sample_ts.u8"),
+            KeyVariant::Traditional,
+        )
+        .unwrap();
+
+        let hao = dictionary.lookup_with_fallback("好", &unihan);
+        assert!(matches!(hao[0], Cow::Borrowed(_)));
+
+        let qiu = dictionary.lookup_with_fallback("㐀", &unihan);
+        assert_eq!(qiu.len(), 1);
+        assert_eq!(qiu[0].pinyin_tone_number, vec!["qiu1"]);
+        assert_eq!(
+            qiu[0].translations,
+            vec!["(same as U+4E18 丘) hillock or mound"]
+        );
+        assert_eq!(qiu[0].layer.as_deref(), Some(UNIHAN_LAYER));
+
+        assert!(dictionary.lookup_with_fallback("㐀㐀", &unihan).is_empty());
+    }
+}
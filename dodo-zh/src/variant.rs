@@ -1,100 +1,139 @@
 use crate::Error;
-use crate::cedict::{Dictionary, Item};
-use std::collections::HashMap;
+use crate::cedict::{Dictionary, Overlay};
+use crate::segmenter::Segmenter;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::sync::OnceLock;
-
-// Static variable to handle the different versions of the dictionaries.
-pub(crate) static SIMPLIFIED: OnceLock<HashMap<String, Item>> = OnceLock::new();
-pub(crate) static TRADTIONAL: OnceLock<HashMap<String, Item>> = OnceLock::new();
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Static variable to handle the dictionaries which are indexed by both versions of chinese.
+static DICTIONARIES: OnceLock<Mutex<Vec<CachedDictionary>>> = OnceLock::new();
+
+/// CachedDictionary is a dictionary shared by the functions of the crate which take the path of the cedict file.
+/// The dictionaries are cached by their path and their overlays
+#[derive(Debug)]
+struct CachedDictionary {
+    path: PathBuf,
+    overlays: Vec<Overlay>,
+    dictionary: Arc<Dictionary>,
+}
 
 /// KeyVariant handle the different supported version of chinese.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
 pub enum KeyVariant {
+    #[default]
     Simplified,
     Traditional,
 }
 
-impl Default for KeyVariant {
-    fn default() -> Self {
-        Self::Simplified
-    }
-}
-
-/// Initialize Dictionaries for simplified & traditional chinese based on the given cedict file path
+/// Initialize the dictionary for simplified & traditional chinese based on the given cedict file path
+/// and the overlays which are stacked on top of it. The file is only parsed by the first call with the
+/// same path and the same overlays. The dictionaries are kept for the lifetime of the program
 ///
 /// # Arguments
 ///
 /// * `path` - &PathBuf
-pub(crate) fn initialize_dictionaries(path: &PathBuf) -> Result<(), Error> {
-    let simplified = Dictionary::new(path, KeyVariant::Simplified)?;
-    let traditional = Dictionary::new(path, KeyVariant::Traditional)?;
+/// * `overlays` - &[Overlay]
+pub(crate) fn initialize_dictionaries(
+    path: &PathBuf,
+    overlays: &[Overlay],
+) -> Result<Arc<Dictionary>, Error> {
+    // A poisoned lock only mean that a loading has panicked. The cached dictionaries are still complete
+    let mut dictionaries = DICTIONARIES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+
+    if let Some(cached) = dictionaries
+        .iter()
+        .find(|cached| cached.path == *path && cached.overlays == overlays)
+    {
+        return Ok(Arc::clone(&cached.dictionary));
+    }
 
-    SIMPLIFIED.get_or_init(|| simplified.items);
-    TRADTIONAL.get_or_init(|| traditional.items);
+    let mut dictionary = Dictionary::new(path, KeyVariant::Traditional)?;
+    dictionary.apply_overlays(overlays);
 
-    Ok(())
+    let dictionary = Arc::new(dictionary);
+    dictionaries.push(CachedDictionary {
+        path: path.to_owned(),
+        overlays: overlays.to_vec(),
+        dictionary: Arc::clone(&dictionary),
+    });
+
+    Ok(dictionary)
 }
 
-impl KeyVariant {
-    /// Convert a text to a desired variant e.g: simplified -> traditional
+impl Dictionary {
+    /// Convert a text to a desired variant e.g: simplified -> traditional. The text is split into the words of
+    /// the input variant so that a character is converted with the word it belongs to e.g: 头发 -> 頭髮 and not 頭發
     ///
     /// # Arguments
     ///
+    /// * `&self` - Self
     /// * `text` - S
     /// * `input_variant` - KeyVariant
     /// * `target_variant`- KeyVariant
-    pub(crate) fn convert_text_to_desired_variant<S: AsRef<str>>(
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dodo_zh::cedict::Dictionary;
+    /// use dodo_zh::variant::KeyVariant;
+    ///
+    /// let dictionary = Dictionary::from_str("頭髮 头发 [tou2 fa5] /hair/", KeyVariant::Simplified).unwrap();
+    /// let converted = dictionary.convert_text_to_variant("头发", KeyVariant::Simplified, KeyVariant::Traditional);
+    /// assert_eq!(converted, "頭髮");
+    /// ```
+    pub fn convert_text_to_variant<S: AsRef<str>>(
+        &self,
         text: S,
-        input_variant: Self,
-        target_variant: Self,
-    ) -> Option<String> {
-        // Split the content as a list of character
-        let chars = text.as_ref().chars();
-
-        // Use the variant that needed for the conversion
-        let dictionary = match input_variant {
-            KeyVariant::Simplified => SIMPLIFIED.get()?,
-            KeyVariant::Traditional => TRADTIONAL.get()?,
-        };
+        input_variant: KeyVariant,
+        target_variant: KeyVariant,
+    ) -> String {
+        let tokens = Segmenter::new(self)
+            .with_variant(input_variant)
+            .segment(text.as_ref());
 
         let mut rebuild_content: Vec<String> = Vec::new();
-        // Rebuild the list of character into the target variant
-        for c in chars {
-            match dictionary.get(&c.to_string()) {
-                Some(character) => {
-                    rebuild_content.push(character.get_character_for_key_variant(&target_variant))
+        // Rebuild the list of words into the target variant. When a word has several entries
+        // the first entry of the cedict file is used.
+        for token in tokens {
+            match token.items.first() {
+                Some(word) => {
+                    rebuild_content.push(word.get_character_for_key_variant(&target_variant))
                 }
                 // We assume that this may be a non chinese character e.g: space or number.
-                None => rebuild_content.push(c.to_string()),
+                None => rebuild_content.push(token.text.to_string()),
             }
         }
 
-        Some(rebuild_content.join(""))
+        rebuild_content.join("")
     }
 
-    /// Which variant returns the variant of chinese that the text has been written on
+    /// Return the variant of chinese that the text has been written on. The text is traditional as soon
+    /// as a character is only a traditional headword of the dictionary
     ///
     /// # Arguments
     ///
+    /// * `&self` - Self
     /// * `text` - S
-    pub(crate) fn which_variant<S: AsRef<str>>(text: S) -> Option<Self> {
-        // Get the simplified dictionary
-        let (simplified_dict, traditional_dict) = SIMPLIFIED.get().zip(TRADTIONAL.get())?;
-
+    pub fn which_variant<S: AsRef<str>>(&self, text: S) -> KeyVariant {
         let characters = text.as_ref().chars();
         for ch in characters {
             let str_char = ch.to_string();
             // Once we found that the variant is traditional. We directly returns the new variant.
-            if simplified_dict.get(&str_char).is_none() && traditional_dict.get(&str_char).is_some()
+            if !self.contains_with_variant(&str_char, &KeyVariant::Simplified)
+                && self.contains_with_variant(&str_char, &KeyVariant::Traditional)
             {
-                return Some(Self::Traditional);
+                return KeyVariant::Traditional;
             }
         }
 
-        Some(Self::Simplified)
+        KeyVariant::Simplified
     }
+}
 
+impl KeyVariant {
     /// Detect the chinese character variant by using Unicode
     ///
     /// # Arguments
@@ -128,15 +167,15 @@ mod tests {
     fn expect_to_transform_traditional_to_simplified() {
         let text = "她是我的最好摯友";
 
-        let res = super::initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"));
-        assert!(res.is_ok());
+        let dictionary =
+            super::initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"), &[])
+                .unwrap();
 
-        let converted = super::KeyVariant::convert_text_to_desired_variant(
+        let converted = dictionary.convert_text_to_variant(
             text,
             KeyVariant::Traditional,
             KeyVariant::Simplified,
-        )
-        .unwrap();
+        );
 
         assert_eq!(converted, "她是我的最好挚友");
     }
@@ -145,35 +184,57 @@ mod tests {
     fn expect_to_convert_simplified_into_traditional() {
         let text = "她是我的最好挚友";
 
-        let res = super::initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"));
-        assert!(res.is_ok());
+        let dictionary =
+            super::initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"), &[])
+                .unwrap();
 
-        let converted = super::KeyVariant::convert_text_to_desired_variant(
+        let converted = dictionary.convert_text_to_variant(
             text,
             KeyVariant::Simplified,
             KeyVariant::Traditional,
-        )
-        .unwrap();
+        );
 
         assert_eq!(converted, "她是我的最好摯友");
     }
 
     #[test]
     fn expect_to_detect_traditional() {
-        let res = initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"));
-        assert!(res.is_ok());
-
-        let res = super::KeyVariant::which_variant("她是我的最好摯友");
-        assert_eq!(res.unwrap(), KeyVariant::Traditional);
+        let dictionary =
+            initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"), &[]).unwrap();
+
+        assert_eq!(
+            dictionary.which_variant("她是我的最好摯友"),
+            KeyVariant::Traditional
+        );
+        assert_eq!(
+            dictionary.which_variant("她是我的最好挚友"),
+            KeyVariant::Simplified
+        );
     }
 
     #[test]
-    fn expect_to_detect_simplified() {
-        let res = initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8"));
-        assert!(res.is_ok());
-
-        let res = super::KeyVariant::which_variant("她是我的最好挚友");
-        assert_eq!(res.unwrap(), KeyVariant::Simplified);
+    fn expect_to_cache_each_dictionary() {
+        let path = PathBuf::from("../static/cedict_sample_ts.u8");
+        let dictionary = initialize_dictionaries(&path, &[]).unwrap();
+        assert!(Arc::ptr_eq(
+            &dictionary,
+            &initialize_dictionaries(&path, &[]).unwrap()
+        ));
+
+        let overlay = Overlay::from_reader(
+            "terms",
+            "行 行 [hang2] /company/".as_bytes(),
+            crate::cedict::OverlayPriority::Replace,
+        )
+        .unwrap();
+        let with_overlay = initialize_dictionaries(&path, &[overlay]).unwrap();
+        assert_eq!(with_overlay.get("行")[0].translations, vec!["company"]);
+        assert_ne!(dictionary.get("行")[0].translations, vec!["company"]);
+
+        let compressed =
+            initialize_dictionaries(&PathBuf::from("../static/cedict_sample_ts.u8.gz"), &[])
+                .unwrap();
+        assert_eq!(compressed.len(), dictionary.len());
     }
 
     #[test]
@@ -0,0 +1,151 @@
+// Constant
+const INITIALS: [&str; 19] = [
+    "ng", "gw", "kw", "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "w", "z", "c", "s",
+    "j",
+];
+const VOWELS: [char; 6] = ['a', 'e', 'i', 'o', 'u', 'y'];
+// Tones which add an `h` after the vowels e.g: nei5 -> néih
+const LOW_TONES: [u32; 3] = [4, 5, 6];
+
+/// Yale convert a single jyutping syllable with a tone number e.g: nei5 into the Yale romanization
+/// of Cantonese with tone marks e.g: néih
+pub struct Yale<S>(pub S)
+where
+    S: AsRef<str>;
+
+impl<S> Yale<S>
+where
+    S: AsRef<str>,
+{
+    /// Convert a single jyutping to a yale value
+    ///     1. Convert the initial e.g: z -> j
+    ///     2. Convert the final e.g: oeng -> eung
+    ///     3. Add the tone mark on the first vowel and the `h` of the low tones
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Self
+    pub fn convert_jyutping_to_yale(&self) -> Option<String> {
+        let jyutping = self.0.as_ref().to_lowercase();
+        let tone = jyutping.chars().last()?.to_digit(10)?;
+        if !(1..=6).contains(&tone) {
+            return None;
+        }
+
+        let syllable = &jyutping[..jyutping.len() - 1];
+        let (initial, final_part) = match INITIALS.iter().find(|i| syllable.starts_with(*i)) {
+            // Syllabic nasals such as m4 or ng5 do not have a final
+            Some(initial) if syllable.len() > initial.len() => {
+                (*initial, &syllable[initial.len()..])
+            }
+            _ => ("", syllable),
+        };
+
+        let initial = match (initial, final_part) {
+            ("j", f) if f.starts_with("yu") => "",
+            ("j", _) => "y",
+            ("z", _) => "j",
+            ("c", _) => "ch",
+            (i, _) => i,
+        };
+
+        let final_part = match final_part {
+            "aa" => "a".to_string(),
+            f => f
+                .replace("eoi", "eui")
+                .replace("eo", "eu")
+                .replace("oe", "eu"),
+        };
+
+        // Split the final between the vowels which carry the tone and the ending consonants
+        let vowels_len = final_part
+            .find(|c: char| !VOWELS.contains(&c))
+            .unwrap_or(final_part.len());
+        let (vowels, ending) = match vowels_len {
+            0 => (final_part.as_str(), ""),
+            len => final_part.split_at(len),
+        };
+
+        let mut marked = String::new();
+        let mut is_marked = false;
+        for c in vowels.chars() {
+            if !is_marked && c != 'y' {
+                marked.push_str(&add_tone_mark(c, tone));
+                is_marked = true;
+            } else {
+                marked.push(c);
+            }
+        }
+
+        if LOW_TONES.contains(&tone) {
+            marked.push('h');
+        }
+
+        Some(format!("{initial}{marked}{ending}"))
+    }
+}
+
+/// Add the tone mark of the yale romanization on a character. Tones 3 and 6 do not have a mark
+///
+/// # Arguments
+///
+/// * `c` - char
+/// * `tone` - u32
+fn add_tone_mark(c: char, tone: u32) -> String {
+    let marked = match (c, tone) {
+        ('a', 1) => 'ā',
+        ('a', 2 | 5) => 'á',
+        ('a', 4) => 'à',
+        ('e', 1) => 'ē',
+        ('e', 2 | 5) => 'é',
+        ('e', 4) => 'è',
+        ('i', 1) => 'ī',
+        ('i', 2 | 5) => 'í',
+        ('i', 4) => 'ì',
+        ('o', 1) => 'ō',
+        ('o', 2 | 5) => 'ó',
+        ('o', 4) => 'ò',
+        ('u', 1) => 'ū',
+        ('u', 2 | 5) => 'ú',
+        ('u', 4) => 'ù',
+        // Syllabic nasals do not have precomposed characters for every tones. Hence a combining mark is used
+        (_, 1) => return format!("{c}\u{0304}"),
+        (_, 2 | 5) => return format!("{c}\u{0301}"),
+        (_, 4) => return format!("{c}\u{0300}"),
+        _ => c,
+    };
+
+    marked.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_to_convert_jyutping_to_yale() {
+        let yales = [
+            "nei5", "hou2", "gwong2", "dung1", "waa2", "jyut6", "jyu5", "hoeng1", "jan4",
+        ]
+        .into_iter()
+        .filter_map(|jyutping| Yale(jyutping).convert_jyutping_to_yale())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            yales,
+            vec![
+                "néih", "hóu", "gwóng", "dūng", "wá", "yuht", "yúh", "hēung", "yàhn"
+            ]
+        );
+    }
+
+    #[test]
+    fn expect_to_convert_syllabic_nasals() {
+        assert_eq!(Yale("m4").convert_jyutping_to_yale().unwrap(), "m\u{0300}h");
+        assert_eq!(
+            Yale("ng5").convert_jyutping_to_yale().unwrap(),
+            "n\u{0301}gh"
+        );
+        assert!(Yale("nei7").convert_jyutping_to_yale().is_none());
+    }
+}
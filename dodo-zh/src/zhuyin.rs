@@ -94,17 +94,9 @@ impl Zhuyin {
             // Handle special cases for the initials.
             match buf_str {
                 // Match whether the pinyin would begin by "zh, ch, sh"
-                "z" | "c" | "s" => {
-                    if next == "h" {
-                        continue;
-                    }
-                }
+                "z" | "c" | "s" if next == "h" => continue,
                 // Used to match the "zhi, chi, shi" bopomofo sound
-                "zh" | "ch" | "sh" => {
-                    if next == "i" {
-                        continue;
-                    }
-                }
+                "zh" | "ch" | "sh" if next == "i" => continue,
                 _ => {}
             }
 
@@ -119,35 +111,22 @@ impl Zhuyin {
             match buf_str {
                 // Check whether "a, e, ya" can be match with the sound "ai, ao, yao...". Cases below
                 // also handle other specific bopomofo rules.
-                "a" | "e" | "ya" => {
+                "a" | "e" | "ya"
                     if next == "i"
                         || (next == "o" && (buf_str == "a" || buf_str == "ya"))
                         || next == "n"
-                        || (next == "r" && buf_str == "e")
-                    {
-                        continue;
-                    }
-                }
-                "o" => {
-                    if next == "u" || next == "n" {
-                        continue;
-                    }
-                }
-                "an" | "en" | "on" | "yan" | "ian" | "yin" | "in" | "wan" | "wen" | "uan" => {
-                    if next == "g" {
-                        continue;
-                    }
-                }
-                "u" => {
-                    if next == "a" || next == "o" || next == "i" || next == "n" {
-                        continue;
-                    }
+                        || (next == "r" && buf_str == "e") =>
+                {
+                    continue;
                 }
-                "w" => {
-                    if next == "u" || next == "a" || next == "o" || next == "e" {
-                        continue;
-                    }
+                "o" if next == "u" || next == "n" => continue,
+                "an" | "en" | "on" | "yan" | "ian" | "yin" | "in" | "wan" | "wen" | "uan"
+                    if next == "g" =>
+                {
+                    continue;
                 }
+                "u" if next == "a" || next == "o" || next == "i" || next == "n" => continue,
+                "w" if next == "u" || next == "a" || next == "o" || next == "e" => continue,
                 _ => {}
             }
 
@@ -0,0 +1,41 @@
+use super::{CommandRunner, DiffArgs, ReportFormat};
+use anyhow::Result;
+use dodo_zh::variant::KeyVariant;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct Diff {
+    args: DiffArgs,
+}
+
+impl Diff {
+    pub fn new(args: DiffArgs) -> Self {
+        Self { args }
+    }
+}
+
+impl CommandRunner for Diff {
+    async fn run(&self) -> Result<()> {
+        let old = dodo_zh::load_cedict_dictionary(
+            PathBuf::from(&self.args.old_path),
+            KeyVariant::Traditional,
+        )?;
+        let new = dodo_zh::load_cedict_dictionary(
+            PathBuf::from(&self.args.new_path),
+            KeyVariant::Traditional,
+        )?;
+
+        let diff = old.diff(&new);
+        let output = match self.args.output_format {
+            ReportFormat::Text => format!("{diff}\n"),
+            ReportFormat::Json => serde_json::to_string_pretty(&diff)?,
+        };
+
+        match &self.args.output_path {
+            Some(path) => std::fs::write(path, output)?,
+            None => print!("{output}"),
+        }
+
+        Ok(())
+    }
+}
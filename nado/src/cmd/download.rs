@@ -1,15 +1,17 @@
 use super::{CommandRunner, DownloadArgs};
 use crate::progress::ProgressBuilder;
 use anyhow::{Result, anyhow};
+use dodo_zh::cedict::DecompressReader;
 use futures_util::StreamExt;
 use std::fs::{self, File};
-use std::io::{ErrorKind, Write};
-use std::process::Command;
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
 use tempfile::Builder;
 
 // Constant
 const URL: &str = "https://www.mdbg.net/chinese/export/cedict/cedict_1_0_ts_utf-8_mdbg.zip";
 const CEDICT: &str = "cedict";
+const CEDICT_FILE_NAME: &str = "cedict_ts.u8";
 
 pub struct Downloader {
     args: DownloadArgs,
@@ -57,31 +59,25 @@ impl CommandRunner for Downloader {
             })
             .map_err(|err| anyhow!("Unable to create the directory {:?}", err))?;
 
-        // Unzip the file to the targeted path
-        #[cfg(any(target_os = "macos", target_os = "linux"))]
-        let output = Command::new("unzip")
-            .arg("-j")
-            .arg("-o")
-            .arg(fname.to_str().unwrap())
-            .arg("-d")
-            .arg(&self.args.output_path)
-            .output()
-            .map_err(|err| anyhow!("Expect to unzip the targeted cedict file {err}"))?;
+        // Decompress the archive to the targeted path
+        let mut archive = DecompressReader::new(File::open(&fname)?)?;
+        let file_name = archive
+            .file_name()
+            .and_then(|name| Path::new(name).file_name())
+            .map(|name| name.to_os_string())
+            .unwrap_or_else(|| CEDICT_FILE_NAME.into());
 
-        #[cfg(target_os = "windows")]
-        let output = Command::new("expand")
-            .arg(fname.to_str().unwrap())
-            .arg(&self.args.output_path)
-            .output()
-            .map_err(|err| anyhow!("Expect to unzip the targeted cedict file {err}"))?;
-
-        match output.status.success() {
-            true => println!("📚 - Dictionary has been downloaded"),
-            false => println!("📚❌ - Dictionary could not be download"),
-        }
+        let target = Path::new(&self.args.output_path).join(file_name);
+        io::copy(&mut archive, &mut File::create(&target)?)
+            .map_err(|err| anyhow!("Expect to decompress the targeted cedict file {err}"))?;
 
         pb.clear();
 
+        println!(
+            "📚 - Dictionary has been downloaded to {}",
+            target.display()
+        );
+
         Ok(())
     }
 }
@@ -2,13 +2,17 @@ use super::{CommandRunner, GenerateArgs, OutputFormat};
 use crate::hsk;
 use crate::progress::ProgressBuilder;
 use crate::{hsk::HSKLevel, util};
-use anyhow::Result;
-use dodo_zh::cedict::Item;
+use anyhow::{Result, anyhow};
+use dodo_zh::cedict::{
+    Dictionary, DictionaryMetadata, FrequencyList, GlossLanguage, Item, MultilingualDictionary,
+    Overlay, OverlayPriority,
+};
 use dodo_zh::variant::KeyVariant;
 use serde::Serialize;
-use std::{collections::HashMap, path::PathBuf};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
 
-const CSV_HEADERS: [&str; 8] = [
+const CSV_HEADERS: [&str; 13] = [
     "traditional_character",
     "simplified_character",
     "pinyin_tone_number",
@@ -16,7 +20,12 @@ const CSV_HEADERS: [&str; 8] = [
     "pinyin_tone_mark",
     "zhuyins",
     "wade_giles",
+    "jyutping",
+    "yale",
     "hsk_level",
+    "layer",
+    "frequency_rank",
+    "frequency_per_million",
 ];
 
 #[derive(Debug)]
@@ -31,7 +40,18 @@ pub struct CedictItem {
     pub pinyin_tone_marker: Vec<String>,
     pub zhuyins: Vec<String>,
     pub wades: Vec<String>,
+    pub yales: Vec<String>,
     pub hsk_level: Option<HSKLevel>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub glosses: BTreeMap<String, Vec<String>>,
+}
+
+/// Metadata and attribution of the cedict dictionary which are written alongside the generated file
+/// in order to comply with the CC-BY-SA license
+#[derive(Debug, Serialize)]
+struct GeneratedMetadata<'a> {
+    metadata: &'a DictionaryMetadata,
+    attribution: String,
 }
 
 impl Gen {
@@ -44,9 +64,47 @@ impl CommandRunner for Gen {
     async fn run(&self) -> Result<()> {
         let path = PathBuf::from(&self.args.file_path);
 
+        let overlays = self
+            .args
+            .overlays
+            .iter()
+            .map(|overlay| parse_overlay(overlay))
+            .collect::<Result<Vec<_>>>()?;
+
         println!("📖 - Loading cedict dictionary");
-        // Load the Cedict dictionary
-        let mut cedict = dodo_zh::load_cedict_dictionary(path, KeyVariant::Traditional)?;
+        // Load the Cedict dictionary with the overlays stacked on top of it
+        let cedict = dodo_zh::load_cedict_dictionary_with_overlays(
+            path,
+            KeyVariant::Traditional,
+            &overlays,
+        )?;
+        let mut cedict = cedict.with_language(self.args.language.parse()?);
+        let metadata = cedict.metadata.clone();
+
+        // Load the word frequency list in order to rank the entries
+        if let Some(path) = &self.args.frequency {
+            println!("📖 - Loading frequency list {path}");
+            cedict.apply_frequencies(&FrequencyList::new(&PathBuf::from(path))?);
+        }
+
+        let mut cedict_items = cedict.into_items();
+        if let Some(max_rank) = self.args.max_rank {
+            cedict_items = dodo_zh::cedict::filter_by_frequency(cedict_items, max_rank);
+        }
+
+        if self.args.sort_by_frequency {
+            dodo_zh::cedict::sort_by_frequency(&mut cedict_items);
+        }
+
+        // Load the dictionaries in other languages e.g: CFDICT or HanDeDict
+        let glosses = self
+            .args
+            .glosses
+            .iter()
+            .map(|gloss| load_gloss_dictionary(gloss))
+            .collect::<Result<Vec<_>>>()?;
+        let multilingual =
+            MultilingualDictionary::from_dictionaries(KeyVariant::Traditional, &glosses);
 
         // Load the HSK level per character
         let hsks = hsk::load_hsk_levels().await.unwrap_or_else(|_| {
@@ -56,12 +114,11 @@ impl CommandRunner for Gen {
         });
 
         println!("⚙️ - Processing cedict items...");
-        let mut pb = ProgressBuilder::new(cedict.items.len() as u64);
+        let mut pb = ProgressBuilder::new(cedict_items.len() as u64);
 
-        let items = cedict
-            .items
-            .drain()
-            .map(|(_, item)| {
+        let items = cedict_items
+            .into_iter()
+            .map(|item| {
                 let citem = CedictItem {
                     cedict_item: item,
                     ..Default::default()
@@ -69,6 +126,8 @@ impl CommandRunner for Gen {
                 .generate_pinyin_tone_marker()
                 .generate_zhuyin_from_pinyin()
                 .generate_wade_giles_from_pinyin()
+                .generate_yale_from_jyutping()
+                .fill_glosses(&multilingual)
                 .fill_hsk_field(&hsks);
 
                 pb.inc(1);
@@ -86,15 +145,99 @@ impl CommandRunner for Gen {
 
         let output = match self.args.output_format {
             OutputFormat::Json => serde_json::to_string(&items)?,
-            OutputFormat::Csv => util::as_csv_string(&items, Some(CSV_HEADERS.to_vec()))?,
+            OutputFormat::Csv => {
+                let gloss_headers = multilingual
+                    .languages()
+                    .iter()
+                    .map(|language| format!("translations_{}", language.code()))
+                    .collect::<BTreeSet<_>>();
+
+                let headers = CSV_HEADERS
+                    .into_iter()
+                    .chain(gloss_headers.iter().map(String::as_str))
+                    .collect::<Vec<_>>();
+
+                util::as_csv_string(&items, Some(headers))?
+            }
         };
 
         std::fs::write(&self.args.output_path, output)?;
 
+        // The attribution is written in a sidecar file so that the generated file keep a standard format
+        if !self.args.without_metadata {
+            let path = metadata_path(&self.args.output_path);
+            println!(
+                "🖊️ - Writing the attribution with the path {}",
+                path.display()
+            );
+
+            let generated = GeneratedMetadata {
+                metadata: &metadata,
+                attribution: metadata.attribution(),
+            };
+            std::fs::write(path, serde_json::to_string_pretty(&generated)?)?;
+        }
+
         Ok(())
     }
 }
 
+/// Parse an overlay argument with the format `[add|replace|delete|readings:]path` and load the overlay file.
+/// The name of the overlay is the path of the file
+///
+/// # Arguments
+///
+/// * `arg` - &str
+fn parse_overlay(arg: &str) -> Result<Overlay> {
+    let (priority, path) = match arg.split_once(':') {
+        Some(("add", path)) => (OverlayPriority::Add, path),
+        Some(("replace", path)) => (OverlayPriority::Replace, path),
+        Some(("delete", path)) => (OverlayPriority::Delete, path),
+        Some(("readings", path)) => (OverlayPriority::Readings, path),
+        _ => (OverlayPriority::Replace, arg),
+    };
+
+    let overlay = Overlay::new(path, &PathBuf::from(path), priority)?;
+
+    Ok(overlay)
+}
+
+/// Load a dictionary in an other language with the format `language=path` e.g: `fr=cfdict.u8`.
+/// Malformed lines are skipped as these dictionaries are less strict than cedict
+///
+/// # Arguments
+///
+/// * `arg` - &str
+fn load_gloss_dictionary(arg: &str) -> Result<Dictionary> {
+    let (language, path) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected a gloss with the format language=path but got {arg}"))?;
+
+    let language: GlossLanguage = language.parse()?;
+    println!("📖 - Loading {language} dictionary {path}");
+
+    let (dictionary, diagnostics) =
+        Dictionary::new_lenient(&PathBuf::from(path), KeyVariant::Traditional)?;
+    if !diagnostics.is_empty() {
+        println!(
+            "⚠️ {} lines of {path} could not be parsed",
+            diagnostics.len()
+        );
+    }
+
+    Ok(dictionary.with_language(language))
+}
+
+/// Get the path of the sidecar file which contains the metadata and the attribution of the output e.g:
+/// `cedict.csv` -> `cedict.csv.metadata.json`
+///
+/// # Arguments
+///
+/// * `output_path` - &str
+fn metadata_path(output_path: &str) -> PathBuf {
+    PathBuf::from(format!("{output_path}.metadata.json"))
+}
+
 impl CedictItem {
     /// Generate a list of pinyin with the tone marker
     ///
@@ -150,6 +293,48 @@ impl CedictItem {
         self
     }
 
+    /// Generate a list of yale based on the cantonese jyutping when the item has one
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    fn generate_yale_from_jyutping(&mut self) -> &mut Self {
+        let yales = self
+            .cedict_item
+            .jyutping
+            .iter()
+            .flatten()
+            .filter_map(|j| dodo_zh::convert_jyutping_to_yale(j).ok())
+            .collect::<Vec<String>>();
+
+        self.yales = yales;
+
+        self
+    }
+
+    /// Fill the translations of the other languages. Every languages are filled so that each CSV
+    /// record has the same columns
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Self
+    /// * `multilingual` - &MultilingualDictionary
+    fn fill_glosses(&mut self, multilingual: &MultilingualDictionary) -> &mut Self {
+        let entry = multilingual.find_item(&self.cedict_item);
+
+        for language in multilingual.languages() {
+            let translations = entry
+                .and_then(|entry| entry.translations.get(language))
+                .cloned()
+                .unwrap_or_default();
+
+            self.glosses
+                .insert(language.code().to_string(), translations);
+        }
+
+        self
+    }
+
     /// Fill the hsk level field if the character is found on the dictionary of hsk characters
     ///
     /// # Arguments
@@ -173,7 +358,7 @@ impl util::AsRecord for CedictItem {
             .map(|h| h.to_string())
             .unwrap_or_default();
 
-        vec![
+        let mut record = vec![
             self.cedict_item.traditional_character.to_owned(),
             self.cedict_item.simplified_character.to_owned(),
             self.cedict_item.pinyin_tone_number.join(","),
@@ -181,7 +366,30 @@ impl util::AsRecord for CedictItem {
             self.pinyin_tone_marker.join(","),
             self.zhuyins.join(","),
             self.wades.join(","),
+            self.cedict_item
+                .jyutping
+                .as_ref()
+                .map(|jyutping| jyutping.join(","))
+                .unwrap_or_default(),
+            self.yales.join(","),
             hsk_str,
-        ]
+            self.cedict_item.layer.to_owned().unwrap_or_default(),
+            self.cedict_item
+                .frequency
+                .map(|frequency| frequency.rank.to_string())
+                .unwrap_or_default(),
+            self.cedict_item
+                .frequency
+                .map(|frequency| frequency.per_million.to_string())
+                .unwrap_or_default(),
+        ];
+
+        record.extend(
+            self.glosses
+                .values()
+                .map(|translations| translations.join(",")),
+        );
+
+        record
     }
 }
@@ -1,8 +1,12 @@
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
+use dodo_zh::variant::KeyVariant;
 
+mod diff;
 mod download;
 mod generate;
+mod snapshot;
+mod validate;
 
 /// Command Runner run the command on the given Arguments
 trait CommandRunner {
@@ -26,6 +30,37 @@ struct GenerateArgs {
 
     #[clap(short = 'f', long, value_parser)]
     output_format: OutputFormat,
+
+    /// Overlay file stacked on top of the cedict file with the format `[add|replace|delete|readings:]path`.
+    /// The overlays are applied in the given order and replace the existing entries by default
+    #[clap(short = 'l', long = "overlay", value_parser)]
+    overlays: Vec<String>,
+
+    /// Language of the translations of the cedict file
+    #[clap(long, value_parser, default_value = "en")]
+    language: String,
+
+    /// Dictionary in the cedict format with translations in an other language with the format `language=path`
+    /// e.g: `fr=cfdict.u8`. The translations are added in a column per language
+    #[clap(short = 'g', long = "gloss", value_parser)]
+    glosses: Vec<String>,
+
+    /// Word frequency list e.g: SUBTLEX-CH, BCC or a file with the format `word<TAB>count`
+    #[clap(long, value_parser)]
+    frequency: Option<String>,
+
+    /// Sort the entries from the most common to the least common. Requires a frequency list
+    #[clap(long, value_parser, requires = "frequency")]
+    sort_by_frequency: bool,
+
+    /// Only keep the entries whose frequency rank is lower or equal to the given rank. Requires a frequency list
+    #[clap(long, value_parser, requires = "frequency")]
+    max_rank: Option<usize>,
+
+    /// Do not write the `<output_path>.metadata.json` file which contains the version, the date and the
+    /// CC-BY-SA attribution of the cedict file
+    #[clap(long, value_parser)]
+    without_metadata: bool,
 }
 
 #[derive(clap::Args)]
@@ -43,11 +78,77 @@ struct DownloadArgs {
     download_link: Option<String>,
 }
 
+#[derive(clap::Args)]
+#[command(
+    author = "shigedangao",
+    version = "0.3.0",
+    about = "build a binary snapshot of the cedict.u8 file for an instant loading",
+    long_about = None
+)]
+#[derive(Debug)]
+struct SnapshotArgs {
+    #[clap(short = 'e', long, value_parser)]
+    file_path: String,
+
+    #[clap(short, long, value_parser)]
+    output_path: String,
+
+    #[clap(short, long, value_enum, default_value_t = ScriptVariant::Traditional)]
+    key_variant: ScriptVariant,
+
+    /// Rebuild the snapshot even if it has already been built from the same cedict file
+    #[clap(long, value_parser)]
+    force: bool,
+}
+
+#[derive(clap::Args)]
+#[command(
+    author = "shigedangao",
+    version = "0.3.0",
+    about = "show the entries which have been added, removed or modified between two cedict.u8 files",
+    long_about = None
+)]
+#[derive(Debug)]
+struct DiffArgs {
+    old_path: String,
+
+    new_path: String,
+
+    #[clap(short = 'f', long, value_enum, default_value_t = ReportFormat::Text)]
+    output_format: ReportFormat,
+
+    #[clap(short, long, value_parser)]
+    output_path: Option<String>,
+}
+
+#[derive(clap::Args)]
+#[command(
+    author = "shigedangao",
+    version = "0.3.0",
+    about = "check the entries of a cedict.u8 file and exit with an error when an issue is found",
+    long_about = None
+)]
+#[derive(Debug)]
+struct ValidateArgs {
+    #[clap(short = 'e', long, value_parser)]
+    file_path: String,
+
+    #[clap(short = 'f', long, value_enum, default_value_t = ReportFormat::Text)]
+    output_format: ReportFormat,
+
+    /// Exit with an error when a warning is found
+    #[clap(long, value_parser)]
+    deny_warnings: bool,
+}
+
 #[derive(Parser)]
 #[command(name = "nomnom")]
 enum Command {
     Generate(GenerateArgs),
     Download(DownloadArgs),
+    Snapshot(SnapshotArgs),
+    Diff(DiffArgs),
+    Validate(ValidateArgs),
 }
 
 #[derive(Debug, ValueEnum, Clone)]
@@ -56,6 +157,27 @@ enum OutputFormat {
     Csv,
 }
 
+#[derive(Debug, ValueEnum, Clone)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, ValueEnum, Clone)]
+enum ScriptVariant {
+    Simplified,
+    Traditional,
+}
+
+impl From<ScriptVariant> for KeyVariant {
+    fn from(variant: ScriptVariant) -> Self {
+        match variant {
+            ScriptVariant::Simplified => KeyVariant::Simplified,
+            ScriptVariant::Traditional => KeyVariant::Traditional,
+        }
+    }
+}
+
 /// Run the command
 pub async fn run() -> Result<()> {
     let cmd = Command::parse();
@@ -63,6 +185,9 @@ pub async fn run() -> Result<()> {
     match cmd {
         Command::Generate(args) => generate::Gen::new(args).run().await?,
         Command::Download(args) => download::Downloader::new(args).run().await?,
+        Command::Snapshot(args) => snapshot::Snapshot::new(args).run().await?,
+        Command::Diff(args) => diff::Diff::new(args).run().await?,
+        Command::Validate(args) => validate::Validate::new(args).run().await?,
     };
 
     Ok(())
@@ -0,0 +1,61 @@
+use super::{CommandRunner, SnapshotArgs};
+use anyhow::Result;
+use dodo_zh::cedict::Dictionary;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Debug)]
+pub struct Snapshot {
+    args: SnapshotArgs,
+}
+
+impl Snapshot {
+    pub fn new(args: SnapshotArgs) -> Self {
+        Self { args }
+    }
+}
+
+impl CommandRunner for Snapshot {
+    async fn run(&self) -> Result<()> {
+        let path = PathBuf::from(&self.args.file_path);
+
+        // The snapshot is only rebuilt when the cedict file has changed since it has been built
+        if !self.args.force
+            && let Ok(bytes) = std::fs::read(&self.args.output_path)
+            && Dictionary::load_snapshot_for(&bytes, File::open(&path)?).is_ok()
+        {
+            println!(
+                "✅ - Snapshot {} is already up to date with the cedict file",
+                self.args.output_path
+            );
+
+            return Ok(());
+        }
+
+        println!("📖 - Loading cedict dictionary");
+        let start = Instant::now();
+        let cedict = dodo_zh::load_cedict_dictionary(path, self.args.key_variant.clone().into())?;
+
+        println!("⚙️ - Building the snapshot with the indexes...");
+        let output = BufWriter::new(File::create(&self.args.output_path)?);
+        cedict.save_snapshot(output)?;
+
+        println!(
+            "📦 - Snapshot of {} entries written to {} in {:.2?} (cedict checksum: {:08x})",
+            cedict.len(),
+            self.args.output_path,
+            start.elapsed(),
+            cedict.source_checksum()
+        );
+
+        // Check that the snapshot can be loaded back
+        let bytes = std::fs::read(&self.args.output_path)?;
+        let start = Instant::now();
+        Dictionary::load_snapshot(&bytes)?;
+        println!("⚡ - Snapshot loaded back in {:.2?}", start.elapsed());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use super::{CommandRunner, ReportFormat, ValidateArgs};
+use anyhow::{Result, anyhow};
+use dodo_zh::cedict::{Dictionary, Validator};
+use dodo_zh::variant::KeyVariant;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct Validate {
+    args: ValidateArgs,
+}
+
+impl Validate {
+    pub fn new(args: ValidateArgs) -> Self {
+        Self { args }
+    }
+}
+
+impl CommandRunner for Validate {
+    async fn run(&self) -> Result<()> {
+        // The malformed lines are reported as issues instead of stopping the validation
+        let (dictionary, diagnostics) = Dictionary::new_lenient(
+            &PathBuf::from(&self.args.file_path),
+            KeyVariant::Traditional,
+        )?;
+
+        let report = Validator::default().validate_with_diagnostics(&dictionary, &diagnostics);
+        match self.args.output_format {
+            ReportFormat::Text => println!("{report}"),
+            ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+
+        if report.has_errors() || (self.args.deny_warnings && report.warnings() > 0) {
+            return Err(anyhow!(
+                "Validation failed with {} errors and {} warnings",
+                report.errors(),
+                report.warnings()
+            ));
+        }
+
+        Ok(())
+    }
+}